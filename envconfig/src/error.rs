@@ -1,29 +1,291 @@
 //! Errors resulting from calling functions in this crate
 
-use std::{error::Error as StdError, fmt};
+use std::{error::Error as StdError, fmt, io};
+
+use crate::meta::{FieldInfo, StructInfo};
+
+/// Placeholder shown instead of values of secret variables.
+pub(crate) const REDACTED: &str = "<redacted>";
 
 /// Represents an error, that may be returned by `fn init_from_env()` of trait `Envconfig`.
-#[derive(Debug, PartialEq)]
+///
+/// New variants may be added in minor releases, so matches need a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
-    EnvVarMissing { name: &'static str },
-    ParseError { name: &'static str },
+    EnvVarMissing {
+        name: String,
+        /// Existing variables with a similar name, most similar first.
+        suggestions: Vec<String>,
+    },
+    /// The value of a variable cannot be parsed into the field type with `FromStr`.
+    ParseError { name: String },
+    /// The value of a variable is not in the expected format, e.g. a boolean spelled as `maybe`.
+    ///
+    /// `message` describes the expected format, e.g. the accepted values of an `EnvconfigValue` enum.
+    InvalidValue {
+        name: String,
+        value: String,
+        message: String,
+    },
+    /// The value of a variable marked with `#[envconfig(json)]` is not valid JSON for the field type.
+    DeserializeError { name: String, message: String },
+    /// The discriminator variable of a config enum does not match any variant.
+    InvalidVariant {
+        name: String,
+        value: String,
+        choices: Vec<&'static str>,
+    },
+    /// A default value declared with `#[envconfig(default = "...")]` cannot be parsed.
+    InvalidDefault { name: String },
+    /// A `${VAR}` reference in the value of a variable cannot be resolved.
+    UnresolvedReference { name: String, reference: String },
+    /// `${VAR}` references in the value of a variable form a cycle.
+    ReferenceCycle { name: String, cycle: Vec<String> },
+    /// A command-line override is not a `KEY=VALUE` pair.
+    InvalidOverride { arg: String },
+    /// A source of variables (e.g. a configuration file) cannot be parsed.
+    SourceError { message: String },
+    /// A file cannot be read.
+    IoError {
+        /// Path of the file.
+        path: String,
+        kind: io::ErrorKind,
+        message: String,
+    },
+    /// The value of a variable in the environment is not valid Unicode.
+    DecodeError { name: String },
+    /// A value is well-formed but rejected by a validation, e.g. a port outside of an allowed range.
+    ///
+    /// Not returned by this crate, meant for validations of the application.
+    ValidationError { name: String, message: String },
+    /// Variables are given that are not declared on the config.
+    UnknownVars { vars: Vec<UnknownVar> },
+    /// An error occurred in a nested config (`#[envconfig(nested)]`) or in the selected variant of a config enum.
+    Nested {
+        /// Path of the field the error occurred in, e.g. `app.database.replica.port`.
+        path: String,
+        /// The error that occurred, available as [`std::error::Error::source()`].
+        error: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Returns the name of the variable the error is about, if any.
+    #[must_use]
+    pub fn var_name(&self) -> Option<&str> {
+        match self {
+            Error::EnvVarMissing { name, .. }
+            | Error::ParseError { name }
+            | Error::InvalidValue { name, .. }
+            | Error::DeserializeError { name, .. }
+            | Error::InvalidVariant { name, .. }
+            | Error::InvalidDefault { name }
+            | Error::UnresolvedReference { name, .. }
+            | Error::ReferenceCycle { name, .. }
+            | Error::DecodeError { name }
+            | Error::ValidationError { name, .. } => Some(name),
+            Error::Nested { error, .. } => error.var_name(),
+            Error::InvalidOverride { .. }
+            | Error::SourceError { .. }
+            | Error::IoError { .. }
+            | Error::UnknownVars { .. } => None,
+        }
+    }
+
+    /// Returns the path of the field the error occurred in, if it occurred in a nested config.
+    #[must_use]
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            Error::Nested { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Marks the error as occurred in the nested config `field`, described by `info`.
+    ///
+    /// Used by the code generated for `#[envconfig(nested)]` fields to build up [`Error::Nested`].
+    #[must_use]
+    pub fn in_field(self, field: &str, info: &StructInfo) -> Self {
+        match self {
+            Error::Nested { path, error } => Error::Nested {
+                path: format!("{field}.{path}"),
+                error,
+            },
+            error => {
+                let leaf = error.var_name().and_then(|name| {
+                    info.fields.iter().find_map(|info| match info {
+                        FieldInfo::Var(var) if var.name == name => Some(var.field),
+                        _ => None,
+                    })
+                });
+                let path = match leaf {
+                    Some(leaf) => format!("{field}.{leaf}"),
+                    None => field.to_string(),
+                };
+                Error::Nested {
+                    path,
+                    error: Box::new(error),
+                }
+            }
+        }
+    }
+
+    /// Hides the value of a secret variable (`#[envconfig(secret)]`) in the error.
+    ///
+    /// The value and any occurrence of it in the message are replaced with `<redacted>`.
+    /// Messages of [`Error::DeserializeError`] may quote parts of the value, so they are replaced entirely.
+    ///
+    /// Used by the code generated for secret fields.
+    #[must_use]
+    pub fn redacted(self) -> Self {
+        match self {
+            Error::InvalidValue {
+                name,
+                value,
+                message,
+            } => {
+                let message = if value.is_empty() {
+                    message
+                } else {
+                    message.replace(&value, REDACTED)
+                };
+                Error::InvalidValue {
+                    name,
+                    value: REDACTED.to_string(),
+                    message,
+                }
+            }
+            Error::DeserializeError { name, .. } => Error::DeserializeError {
+                name,
+                message: REDACTED.to_string(),
+            },
+            Error::Nested { path, error } => Error::Nested {
+                path,
+                error: Box::new(error.redacted()),
+            },
+            error => error,
+        }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Error::EnvVarMissing { name } => {
-                write!(f, "Environment variable {name} is missing")
+            Error::EnvVarMissing { name, suggestions } => {
+                write!(f, "Environment variable {name} is missing")?;
+                if !suggestions.is_empty() {
+                    write!(f, " (did you mean {}?)", suggestions.join(" or "))?;
+                }
+                Ok(())
             }
             Error::ParseError { name } => {
                 write!(f, "Failed to parse environment variable {name}")
             }
+            Error::InvalidValue {
+                name,
+                value,
+                message,
+            } => {
+                write!(
+                    f,
+                    "Invalid value `{value}` of environment variable {name}: {message}"
+                )
+            }
+            Error::DeserializeError { name, message } => {
+                write!(
+                    f,
+                    "Failed to deserialize environment variable {name}: {message}"
+                )
+            }
+            Error::InvalidVariant {
+                name,
+                value,
+                choices,
+            } => {
+                write!(
+                    f,
+                    "Invalid value `{value}` of environment variable {name}, expected one of: {}",
+                    choices.join(", ")
+                )
+            }
+            Error::InvalidDefault { name } => {
+                write!(f, "Default value of environment variable {name} is invalid")
+            }
+            Error::UnresolvedReference { name, reference } => {
+                write!(
+                    f,
+                    "Environment variable {name} references ${{{reference}}}, which is not set"
+                )
+            }
+            Error::ReferenceCycle { name, cycle } => {
+                write!(
+                    f,
+                    "Environment variable {name} contains a reference cycle: {}",
+                    cycle.join(" -> ")
+                )
+            }
+            Error::InvalidOverride { arg } => {
+                write!(f, "Invalid override `{arg}`, expected KEY=VALUE")
+            }
+            Error::SourceError { message } => write!(f, "{message}"),
+            Error::IoError { path, message, .. } => write!(f, "Failed to read {path}: {message}"),
+            Error::DecodeError { name } => {
+                write!(f, "Environment variable {name} is not valid Unicode")
+            }
+            Error::ValidationError { name, message } => {
+                write!(f, "Invalid environment variable {name}: {message}")
+            }
+            Error::Nested { path, error } => write!(f, "{error} (field `{path}`)"),
+            Error::UnknownVars { vars } => {
+                let vars = vars.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "Unknown variables: {}", vars.join(", "))
+            }
         }
     }
 }
 
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
-        None
+        match self {
+            Error::Nested { error, .. } => Some(&**error),
+            _ => None,
+        }
     }
 }
+
+/// A variable that is not declared on the config, see [`Error::UnknownVars`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVar {
+    /// Name of the variable.
+    pub name: String,
+    /// Declared variables with a similar name, most similar first.
+    pub suggestions: Vec<&'static str>,
+}
+
+impl fmt::Display for UnknownVar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.suggestions.is_empty() {
+            write!(f, " (did you mean {}?)", self.suggestions.join(" or "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents an error, that may be returned by `FromStr` implemented with `#[derive(EnvconfigValue)]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidValueError {
+    /// The value that does not match any variant.
+    pub value: String,
+    /// The accepted values.
+    pub choices: &'static [&'static str],
+}
+
+impl fmt::Display for InvalidValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected one of: {}", self.choices.join(", "))
+    }
+}
+
+impl StdError for InvalidValueError {}
@@ -0,0 +1,62 @@
+//! Reporting of config errors at the start of a binary, see `Envconfig::init_from_env_or_exit()`.
+
+use std::env;
+use std::fmt::Write;
+use std::io::{self, IsTerminal};
+use std::process;
+
+use crate::error::Error;
+use crate::meta::StructInfo;
+
+/// Exit code for configuration errors, `EX_CONFIG` of `sysexits.h`.
+const EX_CONFIG: i32 = 78;
+
+const RED_BOLD: &str = "\x1b[1;31m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Prints the errors of the config described by `info` to stderr and exits with `EX_CONFIG`.
+///
+/// The output is colored if stderr is a terminal and `NO_COLOR` is not set.
+pub(crate) fn exit_with_errors(info: &StructInfo, errors: &[Error]) -> ! {
+    let color = io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+    eprint!("{}", render(info, errors, color));
+    process::exit(EX_CONFIG)
+}
+
+/// Renders one item per error, followed by the doc comment of the field it is about.
+fn render(info: &StructInfo, errors: &[Error], color: bool) -> String {
+    let paint = |style: &str, text: &str| {
+        if color {
+            format!("{style}{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    };
+
+    let problems = if errors.len() == 1 {
+        "1 problem".to_string()
+    } else {
+        format!("{} problems", errors.len())
+    };
+    let mut output = format!(
+        "{} invalid configuration ({problems})\n",
+        paint(RED_BOLD, "error:")
+    );
+    for error in errors {
+        let _ = writeln!(output, "  - {error}");
+        let doc = error.var_name().and_then(|name| find_doc(info, name));
+        for line in doc.into_iter().flat_map(str::lines) {
+            let _ = writeln!(output, "      {}", paint(DIM, line));
+        }
+    }
+    output
+}
+
+/// Finds the doc comment of the field loaded from the variable `name`.
+fn find_doc(info: &StructInfo, name: &str) -> Option<&'static str> {
+    info.vars()
+        .into_iter()
+        .find(|var| var.info.name == name)
+        .and_then(|var| var.info.doc)
+}
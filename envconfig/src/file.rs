@@ -0,0 +1,247 @@
+//! Configuration file sources.
+//!
+//! A `.env` file holds `KEY=VALUE` lines with variable names as keys.
+//! A TOML or YAML document is flattened into the same keyspace that is used for environment variables:
+//! keys of nested tables are joined with `_` and uppercased, so `host` in the `[db]` table
+//! becomes `DB_HOST`. Arrays are kept as JSON, so they can be loaded into fields
+//! marked with `#[envconfig(json)]`.
+//!
+//! The resulting hashmap can be passed to [`Envconfig::init_from_hashmap`](crate::Envconfig::init_from_hashmap),
+//! optionally combined with the environment using [`overlay_env`](crate::overlay_env).
+//!
+//! ```
+//! # #[cfg(feature = "toml")]
+//! # {
+//! use envconfig::Envconfig;
+//!
+//! #[derive(Envconfig)]
+//! struct Config {
+//!     #[envconfig(from = "DB_HOST")]
+//!     db_host: String,
+//!     #[envconfig(from = "DB_PORT", default = "5432")]
+//!     db_port: u16,
+//! }
+//!
+//! let source = envconfig::file::from_toml_str("[db]\nhost = \"localhost\"\nport = 6000").unwrap();
+//! let config = Config::init_from_hashmap(&source).unwrap();
+//! assert_eq!(config.db_host, "localhost");
+//! assert_eq!(config.db_port, 6000);
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::{fs, path::Path};
+
+use crate::error::Error;
+
+/// Parses the content of a `.env` file into a hashmap of variables.
+///
+/// - Empty lines and lines starting with `#` are ignored.
+/// - An optional `export ` prefix is allowed.
+/// - Values may be wrapped in single or double quotes.
+///
+/// # Errors
+/// - A line is not a `KEY=VALUE` pair.
+pub fn from_dotenv_str(content: &str) -> Result<HashMap<String, String>, Error> {
+    let mut vars = HashMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let invalid_line = || Error::SourceError {
+            message: format!(
+                "Invalid .env file: line {} is not a KEY=VALUE pair",
+                index + 1
+            ),
+        };
+        let (key, value) = line.split_once('=').ok_or_else(invalid_line)?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(invalid_line());
+        }
+        vars.insert(key.to_string(), unquote(value.trim()).to_string());
+    }
+    Ok(vars)
+}
+
+/// Reads a `.env` file into a hashmap of variables, see [`from_dotenv_str`].
+///
+/// # Errors
+/// - The file cannot be read.
+/// - A line is not a `KEY=VALUE` pair.
+pub fn from_dotenv_file(path: impl AsRef<Path>) -> Result<HashMap<String, String>, Error> {
+    from_dotenv_str(&read_file(path.as_ref())?)
+}
+
+/// Removes matching surrounding quotes from a value.
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Parses a TOML document into a hashmap of variables.
+///
+/// Requires the `toml` feature.
+///
+/// # Errors
+/// - The document is not valid TOML.
+#[cfg(feature = "toml")]
+pub fn from_toml_str(content: &str) -> Result<HashMap<String, String>, Error> {
+    let table = content
+        .parse::<toml::Table>()
+        .map_err(|err| Error::SourceError {
+            message: format!("Invalid TOML: {err}"),
+        })?;
+    let mut vars = HashMap::new();
+    flatten_toml(&mut vars, None, &table);
+    Ok(vars)
+}
+
+/// Reads a TOML file into a hashmap of variables.
+///
+/// Requires the `toml` feature.
+///
+/// # Errors
+/// - The file cannot be read.
+/// - The file is not valid TOML.
+#[cfg(feature = "toml")]
+pub fn from_toml_file(path: impl AsRef<Path>) -> Result<HashMap<String, String>, Error> {
+    from_toml_str(&read_file(path.as_ref())?)
+}
+
+/// Parses a YAML document into a hashmap of variables.
+///
+/// Requires the `yaml` feature.
+///
+/// # Errors
+/// - The document is not valid YAML.
+/// - The document is not a mapping or contains keys that are not scalars.
+#[cfg(feature = "yaml")]
+pub fn from_yaml_str(content: &str) -> Result<HashMap<String, String>, Error> {
+    let value =
+        serde_yaml::from_str::<serde_yaml::Value>(content).map_err(|err| Error::SourceError {
+            message: format!("Invalid YAML: {err}"),
+        })?;
+    let mut vars = HashMap::new();
+    match value {
+        serde_yaml::Value::Mapping(mapping) => flatten_yaml(&mut vars, None, &mapping)?,
+        serde_yaml::Value::Null => {}
+        _ => {
+            return Err(Error::SourceError {
+                message: "Invalid YAML: expected a mapping at the top level".to_string(),
+            })
+        }
+    }
+    Ok(vars)
+}
+
+/// Reads a YAML file into a hashmap of variables.
+///
+/// Requires the `yaml` feature.
+///
+/// # Errors
+/// - The file cannot be read.
+/// - The file is not valid YAML.
+/// - The document is not a mapping or contains keys that are not scalars.
+#[cfg(feature = "yaml")]
+pub fn from_yaml_file(path: impl AsRef<Path>) -> Result<HashMap<String, String>, Error> {
+    from_yaml_str(&read_file(path.as_ref())?)
+}
+
+fn read_file(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|err| Error::IoError {
+        path: path.display().to_string(),
+        kind: err.kind(),
+        message: err.to_string(),
+    })
+}
+
+#[cfg(feature = "toml")]
+fn flatten_toml(vars: &mut HashMap<String, String>, prefix: Option<&str>, table: &toml::Table) {
+    for (key, value) in table {
+        let name = var_name(prefix, key);
+        let value = match value {
+            toml::Value::Table(table) => {
+                flatten_toml(vars, Some(&name), table);
+                continue;
+            }
+            toml::Value::String(value) => value.clone(),
+            toml::Value::Integer(value) => value.to_string(),
+            toml::Value::Float(value) => value.to_string(),
+            toml::Value::Boolean(value) => value.to_string(),
+            toml::Value::Datetime(value) => value.to_string(),
+            toml::Value::Array(_) => to_json(value),
+        };
+        vars.insert(name, value);
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn flatten_yaml(
+    vars: &mut HashMap<String, String>,
+    prefix: Option<&str>,
+    mapping: &serde_yaml::Mapping,
+) -> Result<(), Error> {
+    for (key, value) in mapping {
+        let key = match key {
+            serde_yaml::Value::String(key) => key.clone(),
+            serde_yaml::Value::Number(key) => key.to_string(),
+            serde_yaml::Value::Bool(key) => key.to_string(),
+            _ => {
+                return Err(Error::SourceError {
+                    message: format!("Invalid YAML: unsupported key {key:?}"),
+                })
+            }
+        };
+        let name = var_name(prefix, &key);
+        let value = match value {
+            serde_yaml::Value::Mapping(mapping) => {
+                flatten_yaml(vars, Some(&name), mapping)?;
+                continue;
+            }
+            serde_yaml::Value::Null => continue,
+            serde_yaml::Value::String(value) => value.clone(),
+            serde_yaml::Value::Number(value) => value.to_string(),
+            serde_yaml::Value::Bool(value) => value.to_string(),
+            serde_yaml::Value::Sequence(_) => to_json(value),
+            serde_yaml::Value::Tagged(tagged) => to_json(&tagged.value),
+        };
+        vars.insert(name, value);
+    }
+    Ok(())
+}
+
+/// Joins a key with the prefix of its table into a variable name, e.g. `DB_HOST`.
+#[cfg(any(feature = "toml", feature = "yaml"))]
+fn var_name(prefix: Option<&str>, key: &str) -> String {
+    let key = key.replace('-', "_").to_uppercase();
+    match prefix {
+        Some(prefix) => format!("{prefix}_{key}"),
+        None => key,
+    }
+}
+
+#[cfg(any(feature = "toml", feature = "yaml"))]
+fn to_json<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).expect("a document value can always be serialized to JSON")
+}
+
+/// Layers the process environment over the provided source.
+///
+/// Environment variables take precedence over the values of the source.
+#[must_use]
+pub fn overlay_env<S: ::std::hash::BuildHasher>(
+    mut source: HashMap<String, String, S>,
+) -> HashMap<String, String, S> {
+    source.extend(crate::utils::env_snapshot());
+    source
+}
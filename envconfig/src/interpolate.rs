@@ -0,0 +1,126 @@
+//! Expansion of `${VAR}` references inside values of variables.
+
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::utils::lookup;
+
+/// Expands `${VAR}` and `${VAR:-default}` references in the value of a variable.
+///
+/// References are resolved against the same source the value is loaded from:
+/// the environment if `hashmap` is `None`, the hashmap otherwise.
+/// Referenced values are expanded recursively, and `$$` stands for a literal `$`.
+/// `${VAR:-default}` falls back to `default` if `VAR` is unset or empty, while `${VAR}`
+/// expands to an empty string if `VAR` is empty, like in a shell.
+///
+/// If the variable is not set, `default` is expanded instead, if given.
+///
+/// Returns a source holding the expanded value under `var_name`, to be passed to the `load_*` functions.
+/// The source is empty if neither the variable nor a default is present.
+///
+/// # Errors
+/// - A reference cannot be resolved
+/// - References form a cycle
+/// - A reference is not terminated with `}`
+/// - A value in the environment is not valid Unicode
+pub fn interpolate_var<S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+    default: Option<&'static str>,
+) -> Result<HashMap<String, String>, Error> {
+    let mut source = HashMap::new();
+    let Some(value) = lookup(var_name, hashmap)?.or_else(|| default.map(str::to_string)) else {
+        return Ok(source);
+    };
+
+    let resolver = Resolver { var_name, hashmap };
+    let expanded = resolver.expand(&value, &mut vec![var_name.to_string()])?;
+    source.insert(var_name.to_string(), expanded);
+    Ok(source)
+}
+
+/// Resolves references of the variable `var_name` against a source.
+struct Resolver<'a, S> {
+    var_name: &'a str,
+    hashmap: Option<&'a HashMap<String, String, S>>,
+}
+
+impl<S: ::std::hash::BuildHasher> Resolver<'_, S> {
+    /// Expands all references in `value`.
+    ///
+    /// `chain` holds the variables currently being expanded, to detect cycles.
+    fn expand(&self, value: &str, chain: &mut Vec<String>) -> Result<String, Error> {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find('$') {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            if let Some(after) = after.strip_prefix('$') {
+                result.push('$');
+                rest = after;
+            } else if let Some(after) = after.strip_prefix('{') {
+                let end = find_closing_brace(after).ok_or_else(|| Error::InvalidValue {
+                    name: self.var_name.to_string(),
+                    value: value.to_string(),
+                    message: "reference is not terminated with `}`".to_string(),
+                })?;
+                result.push_str(&self.resolve(&after[..end], chain)?);
+                rest = &after[end + 1..];
+            } else {
+                result.push('$');
+                rest = after;
+            }
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+
+    /// Resolves the content of a single `${...}` reference.
+    fn resolve(&self, reference: &str, chain: &mut Vec<String>) -> Result<String, Error> {
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+
+        if chain.iter().any(|var| var == name) {
+            let mut cycle = chain.clone();
+            cycle.push(name.to_string());
+            return Err(Error::ReferenceCycle {
+                name: self.var_name.to_string(),
+                cycle,
+            });
+        }
+
+        let value =
+            lookup(name, self.hashmap)?.filter(|value| default.is_none() || !value.is_empty());
+        match value {
+            Some(value) => {
+                chain.push(name.to_string());
+                let expanded = self.expand(&value, chain);
+                chain.pop();
+                expanded
+            }
+            None => match default {
+                Some(default) => self.expand(default, chain),
+                None => Err(Error::UnresolvedReference {
+                    name: self.var_name.to_string(),
+                    reference: name.to_string(),
+                }),
+            },
+        }
+    }
+}
+
+/// Returns the position of the `}` closing a reference, skipping nested references in defaults.
+fn find_closing_brace(value: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in value.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(index),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
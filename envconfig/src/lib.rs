@@ -37,12 +37,40 @@
 //! will not compile.
 
 mod error;
+mod exit;
+pub mod file;
+mod interpolate;
+mod meta;
+mod overrides;
+mod report;
+mod suggest;
+pub mod testing;
 mod traits;
 mod utils;
 
-pub use error::Error;
+#[cfg(feature = "json-schema")]
+mod schema;
+
+#[cfg(feature = "reload")]
+pub mod reload;
+
+pub use error::{Error, InvalidValueError, UnknownVar};
+pub use file::overlay_env;
+pub use interpolate::interpolate_var;
+pub use meta::{EnvVar, FieldInfo, StructInfo, ValueFormat, VarInfo, VariantInfo};
+pub use overrides::env_with_overrides;
+pub use report::{Origin, Provenance, Report};
 pub use traits::Envconfig;
-pub use utils::{load_optional_var, load_var, load_var_with_default};
+pub use utils::{
+    load_bool_var, load_bool_var_with_default, load_bytes_var, load_bytes_var_with_default,
+    load_duration_var, load_duration_var_with_default, load_optional_bool_var,
+    load_optional_bytes_var, load_optional_duration_var, load_optional_value_var,
+    load_optional_var, load_value_var, load_value_var_with_default, load_var,
+    load_var_with_default,
+};
+
+#[cfg(feature = "serde")]
+pub use utils::{load_json_var, load_json_var_with_default, load_optional_json_var};
 
 // re-export derive
-pub use envconfig_derive::Envconfig;
+pub use envconfig_derive::{Envconfig, EnvconfigValue};
@@ -0,0 +1,155 @@
+//! Static description of config structs, generated by the derive macro.
+
+/// Describes a config struct or enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructInfo {
+    /// Name of the struct.
+    pub name: &'static str,
+    /// Doc comment of the struct.
+    pub doc: Option<&'static str>,
+    /// Fields of the struct in declaration order.
+    pub fields: Vec<FieldInfo>,
+}
+
+impl StructInfo {
+    /// Returns all variables in declaration order, including the ones of nested structs
+    /// and of all variants of config enums, together with the paths of their fields.
+    #[must_use]
+    pub fn vars(&self) -> Vec<EnvVar> {
+        let mut vars = Vec::new();
+        self.collect_vars("", &mut vars);
+        vars
+    }
+
+    /// Returns the names of all variables, including the ones of nested structs.
+    #[must_use]
+    pub fn var_names(&self) -> Vec<&'static str> {
+        self.vars().into_iter().map(|var| var.info.name).collect()
+    }
+
+    fn collect_vars(&self, prefix: &str, vars: &mut Vec<EnvVar>) {
+        for field in &self.fields {
+            match field {
+                FieldInfo::Var(var) => vars.push(EnvVar {
+                    path: join(prefix, var.field),
+                    info: var.clone(),
+                }),
+                FieldInfo::Nested { field, info, .. } => {
+                    info.collect_vars(&join(prefix, field), vars);
+                }
+                FieldInfo::Variants { var, variants } => {
+                    // The discriminator belongs to the field holding the enum, like in errors
+                    let path = if prefix.is_empty() {
+                        var.field.to_string()
+                    } else {
+                        prefix.to_string()
+                    };
+                    vars.push(EnvVar {
+                        path,
+                        info: var.clone(),
+                    });
+                    for variant in variants {
+                        if let Some(info) = &variant.info {
+                            info.collect_vars(&join(prefix, variant.value), vars);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A variable of a config together with the path of its field, see [`StructInfo::vars`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvVar {
+    /// Path of the field, e.g. `db.host` or `storage.s3.bucket`, as in [`Error::Nested`](crate::Error::Nested).
+    pub path: String,
+    /// Description of the variable.
+    pub info: VarInfo,
+}
+
+/// Describes a field of a config struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldInfo {
+    /// A field loaded from a single variable.
+    Var(VarInfo),
+    /// A field marked with `#[envconfig(nested)]`.
+    Nested {
+        /// Name of the field.
+        field: &'static str,
+        /// Doc comment of the field.
+        doc: Option<&'static str>,
+        /// Description of the nested struct.
+        info: StructInfo,
+    },
+    /// The discriminator variable of a config enum together with its variants.
+    Variants {
+        /// The discriminator variable.
+        var: VarInfo,
+        /// Variants of the enum.
+        variants: Vec<VariantInfo>,
+    },
+}
+
+/// Describes a variant of a config enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantInfo {
+    /// Name of the variant.
+    pub name: &'static str,
+    /// Value of the discriminator variable selecting the variant.
+    pub value: &'static str,
+    /// Doc comment of the variant.
+    pub doc: Option<&'static str>,
+    /// Description of the config of the variant, `None` for unit variants.
+    pub info: Option<StructInfo>,
+}
+
+/// Format of the value of a variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueFormat {
+    /// Parsed with `FromStr`.
+    FromStr,
+    /// JSON (`#[envconfig(json)]`).
+    Json,
+    /// Boolean accepting spellings like `yes` or `off` (`bool` fields).
+    Bool,
+    /// Human-readable duration like `1h30m` (`#[envconfig(duration)]`).
+    Duration,
+    /// Byte size like `64MiB` (`#[envconfig(bytes)]`).
+    Bytes,
+    /// One of the accepted values of an `EnvconfigValue` enum (`#[envconfig(value)]`).
+    Value,
+}
+
+/// Describes a variable a field is loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarInfo {
+    /// Name of the variable.
+    pub name: &'static str,
+    /// Name of the field.
+    pub field: &'static str,
+    /// Rust type of the field, e.g. `Option<u16>`.
+    pub type_name: &'static str,
+    /// Default value (`#[envconfig(default = "...")]`).
+    pub default: Option<&'static str>,
+    /// Whether loading fails if the variable is not set.
+    pub required: bool,
+    /// Whether the field is taken from the struct's `Default` value if the variable is not set
+    /// (`#[envconfig(default)]` on the struct).
+    pub struct_default: bool,
+    /// Whether the value is secret (`#[envconfig(secret)]`).
+    pub secret: bool,
+    /// Format of the value.
+    pub format: ValueFormat,
+    /// Doc comment of the field.
+    pub doc: Option<&'static str>,
+}
+
+/// Joins the path of a field to the path of the struct holding it.
+pub(crate) fn join(prefix: &str, field: &str) -> String {
+    if prefix.is_empty() {
+        field.to_string()
+    } else {
+        format!("{prefix}.{field}")
+    }
+}
@@ -0,0 +1,71 @@
+//! Command-line overrides of variables.
+
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::suggest::check_known_vars;
+use crate::traits::Envconfig;
+use crate::utils::env_snapshot_for;
+
+/// Builds a source from `KEY=VALUE` overrides layered over the process environment.
+///
+/// Overrides take precedence over environment variables.
+/// Every key must be a variable declared on `C` (including nested configs).
+///
+/// ```
+/// use envconfig::Envconfig;
+///
+/// #[derive(Envconfig)]
+/// struct Config {
+///     #[envconfig(from = "HTTP_PORT", default = "8080")]
+///     http_port: u16,
+/// }
+///
+/// // e.g. the values of `--set HTTP_PORT=9090` arguments
+/// let args = ["HTTP_PORT=9090"];
+///
+/// let source = envconfig::env_with_overrides::<Config, _>(args).unwrap();
+/// let config = Config::init_from_hashmap(&source).unwrap();
+/// assert_eq!(config.http_port, 9090);
+/// ```
+///
+/// # Errors
+/// - An argument is not a `KEY=VALUE` pair.
+/// - A key is not declared on `C`.
+/// - The value of a variable of `C` is not valid Unicode.
+pub fn env_with_overrides<C, I>(args: I) -> Result<HashMap<String, String>, Error>
+where
+    C: Envconfig,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let overrides = parse_overrides(args)?;
+
+    check_known_vars(
+        overrides.keys().map(String::as_str),
+        &C::struct_info().var_names(),
+    )?;
+
+    let mut source = env_snapshot_for::<C>()?;
+    source.extend(overrides);
+    Ok(source)
+}
+
+/// Parses `KEY=VALUE` arguments into a hashmap. Later arguments win.
+fn parse_overrides<I>(args: I) -> Result<HashMap<String, String>, Error>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    args.into_iter()
+        .map(|arg| {
+            let arg = arg.as_ref();
+            match arg.split_once('=') {
+                Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+                _ => Err(Error::InvalidOverride {
+                    arg: arg.to_string(),
+                }),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,246 @@
+//! Reloading of a config at runtime.
+//!
+//! A [`Reloader`] loads a config from a `.env` file (see [`from_dotenv_str`](crate::file::from_dotenv_str)
+//! for the format) and re-runs [`Envconfig::init_from_hashmap`] whenever the file changes or
+//! the process receives `SIGHUP`. Failures are reported as [`Error`].
+//! Readers get the current value through a cheap [`ConfigHandle`].
+//! If a reload fails, the previously loaded config is kept.
+//!
+//! This module is available only with the `reload` feature enabled.
+//!
+//! ```no_run
+//! use envconfig::Envconfig;
+//! use envconfig::reload::Reloader;
+//!
+//! #[derive(Envconfig)]
+//! struct Config {
+//!     #[envconfig(from = "HTTP_PORT", default = "8080")]
+//!     http_port: u16,
+//! }
+//!
+//! let reloader = Reloader::<Config>::new("/etc/myapp/config.env")
+//!     .unwrap()
+//!     .on_error(|err| eprintln!("Failed to reload config: {err}"));
+//! let handle = reloader.handle();
+//!
+//! // Keep the guards alive for as long as reloading is desired
+//! let _watcher = reloader.watch_file().unwrap();
+//! let _sighup = reloader.reload_on_sighup().unwrap();
+//!
+//! println!("Listening on port {}", handle.load().http_port);
+//! ```
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::error::Error;
+use crate::file::from_dotenv_file;
+use crate::traits::Envconfig;
+
+/// A cheap, cloneable handle to the most recently loaded config.
+pub struct ConfigHandle<C> {
+    current: Arc<RwLock<Arc<C>>>,
+}
+
+impl<C> ConfigHandle<C> {
+    /// Returns the current config.
+    ///
+    /// The returned value is a snapshot: it is not affected by later reloads.
+    #[must_use]
+    pub fn load(&self) -> Arc<C> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn store(&self, config: C) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(config);
+    }
+}
+
+impl<C> Clone for ConfigHandle<C> {
+    fn clone(&self) -> Self {
+        Self {
+            current: Arc::clone(&self.current),
+        }
+    }
+}
+
+type ErrorCallback = Arc<dyn Fn(&Error) + Send + Sync>;
+
+/// Reloads a config from a `.env` style file.
+pub struct Reloader<C> {
+    path: PathBuf,
+    handle: ConfigHandle<C>,
+    on_error: Option<ErrorCallback>,
+}
+
+impl<C> Clone for Reloader<C> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            handle: self.handle.clone(),
+            on_error: self.on_error.clone(),
+        }
+    }
+}
+
+impl<C: Envconfig + Send + Sync + 'static> Reloader<C> {
+    /// Loads the config from the file at `path`.
+    ///
+    /// # Errors
+    /// - The file cannot be read or contains an invalid line.
+    /// - The config cannot be initialized from the file.
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let config = load_from_file::<C>(&path)?;
+        Ok(Self {
+            path,
+            handle: ConfigHandle {
+                current: Arc::new(RwLock::new(Arc::new(config))),
+            },
+            on_error: None,
+        })
+    }
+
+    /// Sets a callback that is invoked when a reload triggered in the background fails,
+    /// or when watching the file fails.
+    #[must_use]
+    pub fn on_error(mut self, callback: impl Fn(&Error) + Send + Sync + 'static) -> Self {
+        self.on_error = Some(Arc::new(callback));
+        self
+    }
+
+    /// Returns a handle to the current config.
+    #[must_use]
+    pub fn handle(&self) -> ConfigHandle<C> {
+        self.handle.clone()
+    }
+
+    /// Reloads the config from the file.
+    /// On failure the previously loaded config is kept.
+    ///
+    /// # Errors
+    /// - The file cannot be read or contains an invalid line.
+    /// - The config cannot be initialized from the file.
+    pub fn reload(&self) -> Result<(), Error> {
+        let config = load_from_file::<C>(&self.path)?;
+        self.handle.store(config);
+        Ok(())
+    }
+
+    /// Starts watching the file and reloads the config whenever it changes.
+    /// Watching stops when the returned [`FileWatcher`] is dropped.
+    ///
+    /// The parent directory is watched, so files replaced by editors or
+    /// deployment tools (write to a temporary file, then rename) are picked up too.
+    ///
+    /// # Errors
+    /// - The watcher cannot be created (e.g. the inotify limit is reached).
+    pub fn watch_file(&self) -> io::Result<FileWatcher> {
+        let reloader = self.clone();
+        let file_name = self.path.file_name().map(ToOwned::to_owned);
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) => {
+                    let is_relevant =
+                        matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                            && event
+                                .paths
+                                .iter()
+                                .any(|path| path.file_name().map(ToOwned::to_owned) == file_name);
+                    if is_relevant {
+                        reloader.reload_in_background();
+                    }
+                }
+                Err(err) => {
+                    let err = io_error(err);
+                    reloader.report(&Error::IoError {
+                        path: reloader.path.display().to_string(),
+                        kind: err.kind(),
+                        message: err.to_string(),
+                    });
+                }
+            })
+            .map_err(io_error)?;
+
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(io_error)?;
+
+        Ok(FileWatcher { _watcher: watcher })
+    }
+
+    /// Reloads the config whenever the process receives `SIGHUP`.
+    /// Listening stops when the returned [`SighupListener`] is dropped.
+    ///
+    /// # Errors
+    /// - The signal handler cannot be registered.
+    #[cfg(unix)]
+    pub fn reload_on_sighup(&self) -> io::Result<SighupListener> {
+        let mut signals = signal_hook::iterator::Signals::new([signal_hook::consts::SIGHUP])?;
+        let signals_handle = signals.handle();
+        let reloader = self.clone();
+        let thread = std::thread::spawn(move || {
+            for _ in signals.forever() {
+                reloader.reload_in_background();
+            }
+        });
+        Ok(SighupListener {
+            signals_handle,
+            thread: Some(thread),
+        })
+    }
+
+    fn reload_in_background(&self) {
+        if let Err(err) = self.reload() {
+            self.report(&err);
+        }
+    }
+
+    fn report(&self, err: &Error) {
+        if let Some(on_error) = &self.on_error {
+            on_error(err);
+        }
+    }
+}
+
+/// Keeps a file watcher started by [`Reloader::watch_file`] alive.
+pub struct FileWatcher {
+    _watcher: notify::RecommendedWatcher,
+}
+
+/// Keeps a `SIGHUP` listener started by [`Reloader::reload_on_sighup`] alive.
+#[cfg(unix)]
+pub struct SighupListener {
+    signals_handle: signal_hook::iterator::Handle,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(unix)]
+impl Drop for SighupListener {
+    fn drop(&mut self) {
+        self.signals_handle.close();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Reads the file and initializes the config from it.
+fn load_from_file<C: Envconfig>(path: &Path) -> Result<C, Error> {
+    C::init_from_hashmap(&from_dotenv_file(path)?)
+}
+
+/// Converts an error of the file watcher into an I/O error.
+fn io_error(err: notify::Error) -> io::Error {
+    match err.kind {
+        notify::ErrorKind::Io(err) => err,
+        _ => io::Error::other(err),
+    }
+}
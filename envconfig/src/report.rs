@@ -0,0 +1,184 @@
+//! Provenance of loaded values: which source supplied each field.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::error::REDACTED;
+use crate::meta::{join, FieldInfo, StructInfo, VarInfo};
+
+/// Where the value of a field came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    /// The variable is set in the environment.
+    Environment,
+    /// The variable is set in the hashmap passed to `init_from_hashmap_with_report()`.
+    HashMap,
+    /// The variable is not set, the default value is used.
+    ///
+    /// The value is only known for string and literal defaults, not e.g. for the struct's `Default` value.
+    Default,
+    /// The variable is not set and the field is optional, so it is `None`.
+    Unset,
+    /// The field is a nested config, its fields are reported separately.
+    Nested,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let origin = match self {
+            Origin::Environment => "env",
+            Origin::HashMap => "hashmap",
+            Origin::Default => "default",
+            Origin::Unset => "unset",
+            Origin::Nested => "nested",
+        };
+        write!(f, "{origin}")
+    }
+}
+
+/// Describes where the value of a single field came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    /// Path of the field, e.g. `db.port` for the field `port` of the nested field `db`.
+    pub path: String,
+    /// Name of the variable, `None` for nested configs.
+    pub var: Option<&'static str>,
+    /// Where the value came from.
+    pub origin: Origin,
+    /// The raw value or default, redacted for secret variables.
+    pub value: Option<String>,
+}
+
+/// Describes where the values of all fields of a config came from.
+///
+/// Returned by `Envconfig::init_from_env_with_report()`.
+/// Displays as one line per field, e.g. `db.port (default) DB_PORT = 5432`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    entries: Vec<Provenance>,
+}
+
+impl Report {
+    /// Builds the report of a config described by `info` loaded from `source`.
+    pub(crate) fn new<S: ::std::hash::BuildHasher>(
+        info: &StructInfo,
+        source: &HashMap<String, String, S>,
+        origin: Origin,
+    ) -> Self {
+        let mut builder = Builder {
+            source,
+            origin,
+            entries: Vec::new(),
+        };
+        builder.add_struct(info, "");
+        Self {
+            entries: builder.entries,
+        }
+    }
+
+    /// Returns the provenance of all fields in declaration order, nested fields included.
+    #[must_use]
+    pub fn entries(&self) -> &[Provenance] {
+        &self.entries
+    }
+
+    /// Returns the provenance of the field with the provided path, e.g. `db.port`.
+    #[must_use]
+    pub fn get(&self, path: &str) -> Option<&Provenance> {
+        self.entries.iter().find(|entry| entry.path == path)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for entry in &self.entries {
+            write!(f, "{} ({})", entry.path, entry.origin)?;
+            if let Some(var) = entry.var {
+                write!(f, " {var}")?;
+            }
+            if let Some(value) = &entry.value {
+                write!(f, " = {value}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+struct Builder<'a, S> {
+    source: &'a HashMap<String, String, S>,
+    origin: Origin,
+    entries: Vec<Provenance>,
+}
+
+impl<S: ::std::hash::BuildHasher> Builder<'_, S> {
+    fn add_struct(&mut self, info: &StructInfo, prefix: &str) {
+        for field in &info.fields {
+            match field {
+                FieldInfo::Var(var) => self.add_var(var, &join(prefix, var.field)),
+                FieldInfo::Nested { field, info, .. } => {
+                    let path = join(prefix, field);
+                    // A nested config enum is represented by its discriminator variable
+                    let is_enum = matches!(info.fields.as_slice(), [FieldInfo::Variants { .. }]);
+                    if !is_enum {
+                        self.entries.push(Provenance {
+                            path: path.clone(),
+                            var: None,
+                            origin: Origin::Nested,
+                            value: None,
+                        });
+                    }
+                    self.add_struct(info, &path);
+                }
+                FieldInfo::Variants { var, variants } => {
+                    // A config enum is reported at the path of the field holding it
+                    let path = if prefix.is_empty() {
+                        var.field.to_string()
+                    } else {
+                        prefix.to_string()
+                    };
+                    self.add_var(var, &path);
+
+                    let selected = self
+                        .source
+                        .get(var.name)
+                        .map(String::as_str)
+                        .or(var.default);
+                    let variant = variants.iter().find(|variant| {
+                        selected.is_some_and(|value| value.eq_ignore_ascii_case(variant.value))
+                    });
+                    if let Some(info) = variant.and_then(|variant| variant.info.as_ref()) {
+                        self.add_struct(info, &path);
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_var(&mut self, var: &VarInfo, path: &str) {
+        let (origin, value) = match self.source.get(var.name) {
+            Some(value) => (self.origin, Some(value.as_str())),
+            None if var.default.is_some() || var.struct_default || !is_optional(var) => {
+                (Origin::Default, var.default)
+            }
+            None => (Origin::Unset, None),
+        };
+        let value = value.map(|value| {
+            if var.secret {
+                REDACTED.to_string()
+            } else {
+                value.to_string()
+            }
+        });
+        self.entries.push(Provenance {
+            path: path.to_string(),
+            var: Some(var.name),
+            origin,
+            value,
+        });
+    }
+}
+
+fn is_optional(var: &VarInfo) -> bool {
+    var.type_name.starts_with("Option<")
+}
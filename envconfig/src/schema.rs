@@ -0,0 +1,142 @@
+//! Generation of a [JSON Schema](https://json-schema.org/) from a [`StructInfo`].
+
+use serde_json::{json, Map, Value};
+
+use crate::meta::{FieldInfo, StructInfo, ValueFormat, VarInfo};
+
+/// Builds the JSON Schema document describing the provided struct.
+pub(crate) fn json_schema(info: &StructInfo) -> Value {
+    let mut schema = struct_schema(info);
+    if let Value::Object(ref mut object) = schema {
+        object.insert(
+            "$schema".to_string(),
+            json!("https://json-schema.org/draft/2020-12/schema"),
+        );
+    }
+    schema
+}
+
+fn struct_schema(info: &StructInfo) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    let mut conditions = Vec::new();
+
+    for field in &info.fields {
+        let (name, schema, is_required) = match field {
+            FieldInfo::Var(var) => (var.field, var_schema(var), var.required),
+            FieldInfo::Nested { field, doc, info } => {
+                let mut schema = struct_schema(info);
+                if let (Value::Object(ref mut object), Some(doc)) = (&mut schema, doc) {
+                    object.insert("description".to_string(), json!(doc));
+                }
+                // A nested config can be omitted if all of its fields can
+                let is_required = schema["required"]
+                    .as_array()
+                    .is_some_and(|required| !required.is_empty());
+                (*field, schema, is_required)
+            }
+            FieldInfo::Variants { var, variants } => {
+                let mut schema = var_schema(var);
+                if let Value::Object(ref mut object) = schema {
+                    let values = variants.iter().map(|variant| json!(variant.value));
+                    object.insert("enum".to_string(), Value::Array(values.collect()));
+                }
+                // The fields of the selected variant's config are expected next to the discriminator
+                for variant in variants {
+                    if let Some(info) = &variant.info {
+                        let mut variant_schema = struct_schema(info);
+                        if let (Value::Object(ref mut object), Some(doc)) =
+                            (&mut variant_schema, variant.doc)
+                        {
+                            object.insert("description".to_string(), json!(doc));
+                        }
+                        let mut condition = json!({
+                            "properties": { var.field: { "const": variant.value } },
+                        });
+                        // The default variant is also selected if the discriminator is not set
+                        let is_default = var
+                            .default
+                            .is_some_and(|default| default.eq_ignore_ascii_case(variant.value));
+                        if !is_default {
+                            condition["required"] = json!([var.field]);
+                        }
+                        conditions.push(json!({ "if": condition, "then": variant_schema }));
+                    }
+                }
+                (var.field, schema, var.required)
+            }
+        };
+        if is_required {
+            required.push(json!(name));
+        }
+        properties.insert(name.to_string(), schema);
+    }
+
+    let mut schema = Map::new();
+    schema.insert("title".to_string(), json!(info.name));
+    if let Some(doc) = info.doc {
+        schema.insert("description".to_string(), json!(doc));
+    }
+    schema.insert("type".to_string(), json!("object"));
+    schema.insert("properties".to_string(), Value::Object(properties));
+    schema.insert("required".to_string(), Value::Array(required));
+    if !conditions.is_empty() {
+        schema.insert("allOf".to_string(), Value::Array(conditions));
+    }
+    Value::Object(schema)
+}
+
+fn var_schema(var: &VarInfo) -> Value {
+    let value_type = if matches!(
+        var.format,
+        ValueFormat::Json | ValueFormat::Duration | ValueFormat::Bytes
+    ) {
+        None
+    } else {
+        json_type(inner_type_name(var.type_name))
+    };
+
+    let mut schema = Map::new();
+    schema.insert("type".to_string(), json!(value_type.unwrap_or("string")));
+    if var.format == ValueFormat::Json {
+        schema.insert("contentMediaType".to_string(), json!("application/json"));
+    }
+    if let Some(doc) = var.doc {
+        schema.insert("description".to_string(), json!(doc));
+    }
+    if let Some(default) = var.default {
+        let default = match value_type {
+            Some("integer" | "number" | "boolean") => {
+                serde_json::from_str(default).unwrap_or_else(|_| json!(default))
+            }
+            _ => json!(default),
+        };
+        schema.insert("default".to_string(), default);
+    }
+    if var.secret {
+        schema.insert("writeOnly".to_string(), json!(true));
+    }
+    schema.insert("x-env-var".to_string(), json!(var.name));
+    schema.insert("x-rust-type".to_string(), json!(var.type_name));
+    Value::Object(schema)
+}
+
+/// Strips `Option<...>` from a type name.
+fn inner_type_name(type_name: &str) -> &str {
+    type_name
+        .strip_prefix("Option<")
+        .and_then(|inner| inner.strip_suffix('>'))
+        .unwrap_or(type_name)
+}
+
+/// Maps a Rust type name to a JSON Schema type, if it is a well-known primitive.
+fn json_type(type_name: &str) -> Option<&'static str> {
+    match type_name {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize" => Some("integer"),
+        "f32" | "f64" => Some("number"),
+        "bool" => Some("boolean"),
+        "String" | "char" => Some("string"),
+        _ => None,
+    }
+}
@@ -0,0 +1,119 @@
+//! "Did you mean" suggestions for misspelled variable names.
+
+use crate::error::{Error, UnknownVar};
+
+/// Maximum number of suggestions for a single name.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Returns the candidates similar to `name`, most similar first.
+///
+/// A candidate is similar if it differs only in case, if one name is the other with a prefix
+/// (e.g. `DB_HOST` and `MYAPP_DB_HOST`), or if few characters are inserted, removed, replaced
+/// or transposed after the common beginning of both names. `name` itself is never suggested.
+pub(crate) fn similar_names<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    let upper_name = name.to_uppercase();
+
+    let mut similar = candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .filter_map(|candidate| {
+            let upper_candidate = candidate.to_uppercase();
+            let distance = if upper_candidate == upper_name {
+                0
+            } else if is_prefixed(&upper_candidate, &upper_name)
+                || is_prefixed(&upper_name, &upper_candidate)
+            {
+                1
+            } else {
+                // Names often share long prefixes like `MYAPP_DB_`, which must not make
+                // unrelated endings look similar
+                let (name_rest, candidate_rest) =
+                    strip_common_prefix(&upper_name, &upper_candidate);
+                let max_distance = (name_rest.chars().count() / 3).max(1);
+                let distance = edit_distance(name_rest, candidate_rest);
+                if distance > max_distance {
+                    return None;
+                }
+                distance
+            };
+            Some((distance, candidate))
+        })
+        .collect::<Vec<_>>();
+    similar.sort_unstable();
+    similar.dedup();
+    similar
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Checks that all `names` are `declared`, suggesting similar declared names for unknown ones.
+///
+/// # Errors
+/// - Some of the names are not declared.
+pub(crate) fn check_known_vars<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    declared: &[&'static str],
+) -> Result<(), Error> {
+    let mut unknown = names
+        .into_iter()
+        .filter(|name| !declared.contains(name))
+        .map(|name| UnknownVar {
+            name: name.to_string(),
+            suggestions: similar_names(name, declared.iter().copied()),
+        })
+        .collect::<Vec<_>>();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    unknown.sort_by(|a, b| a.name.cmp(&b.name));
+    Err(Error::UnknownVars { vars: unknown })
+}
+
+/// Removes the longest common prefix from both strings.
+fn strip_common_prefix<'s>(a: &'s str, b: &'s str) -> (&'s str, &'s str) {
+    let len = a
+        .char_indices()
+        .zip(b.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or_else(|| a.len().min(b.len()), |((index, _), _)| index);
+    (&a[len..], &b[len..])
+}
+
+/// Whether `long` is `short` with a prefix separated by `_`.
+fn is_prefixed(long: &str, short: &str) -> bool {
+    long.strip_suffix(short)
+        .is_some_and(|prefix| prefix.ends_with('_'))
+}
+
+/// Computes the optimal string alignment distance: the number of inserted, removed,
+/// replaced or transposed characters needed to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    // `rows[i][j]` is the distance between the first `i` characters of `a` and the first `j` of `b`
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
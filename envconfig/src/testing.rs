@@ -0,0 +1,110 @@
+//! Helpers for tests that change the process environment.
+//!
+//! The environment is global to the process, while tests run in parallel threads.
+//! An [`EnvGuard`] holds a global lock for as long as it lives, so tests using it do not
+//! observe each other's variables. Changed variables are restored when the guard is dropped,
+//! also if the test panics.
+//!
+//! ```
+//! use envconfig::Envconfig;
+//! use envconfig::testing::EnvGuard;
+//!
+//! #[derive(Envconfig)]
+//! struct Config {
+//!     #[envconfig(from = "HTTP_PORT", default = "8080")]
+//!     http_port: u16,
+//! }
+//!
+//! let _env = EnvGuard::new().set("HTTP_PORT", "9090");
+//! assert_eq!(Config::init_from_env().unwrap().http_port, 9090);
+//! ```
+//!
+//! The lock is not reentrant: creating a second guard while one is alive on the same thread deadlocks.
+
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Serializes access to the environment between guards.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Changes variables of the process environment and restores them on drop.
+///
+/// See the [module documentation](self) for an example.
+pub struct EnvGuard {
+    /// Previous values of the changed variables, in the order of the changes
+    saved: Vec<(String, Option<OsString>)>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl EnvGuard {
+    /// Acquires the environment lock, waiting for other guards to be dropped.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            saved: Vec::new(),
+            // A test panicking while holding the lock does not leave the environment changed,
+            // as the guard restores it during unwinding
+            _lock: ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+
+    /// Sets the variable `key` to `value` until the guard is dropped.
+    #[must_use]
+    pub fn set(mut self, key: &str, value: impl AsRef<OsStr>) -> Self {
+        self.save(key);
+        env::set_var(key, value);
+        self
+    }
+
+    /// Removes the variable `key` until the guard is dropped.
+    #[must_use]
+    pub fn remove(mut self, key: &str) -> Self {
+        self.save(key);
+        env::remove_var(key);
+        self
+    }
+
+    fn save(&mut self, key: &str) {
+        self.saved.push((key.to_string(), env::var_os(key)));
+    }
+}
+
+impl Default for EnvGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (key, value) in self.saved.drain(..).rev() {
+            match value {
+                Some(value) => env::set_var(key, value),
+                None => env::remove_var(key),
+            }
+        }
+    }
+}
+
+/// Runs `f` with the provided variables set (`Some`) or removed (`None`),
+/// restoring the environment afterwards.
+///
+/// ```
+/// use envconfig::testing::with_env;
+///
+/// let home = with_env(&[("MY_APP_HOME", Some("/tmp")), ("MY_APP_DEBUG", None)], || {
+///     std::env::var("MY_APP_HOME").unwrap()
+/// });
+/// assert_eq!(home, "/tmp");
+/// ```
+pub fn with_env<R>(vars: &[(&str, Option<&str>)], f: impl FnOnce() -> R) -> R {
+    let mut guard = EnvGuard::new();
+    for (key, value) in vars {
+        guard = match value {
+            Some(value) => guard.set(key, value),
+            None => guard.remove(key),
+        };
+    }
+    f()
+}
@@ -1,4 +1,9 @@
 use crate::error::Error;
+use crate::exit::exit_with_errors;
+use crate::meta::{EnvVar, StructInfo};
+use crate::report::{Origin, Report};
+use crate::suggest::check_known_vars;
+use crate::utils::env_snapshot_for;
 use std::collections::HashMap;
 
 /// Indicates that structure can be initialize from environment variables.
@@ -26,6 +31,108 @@ pub trait Envconfig {
     where
         Self: Sized;
 
+    /// Initialize structure from a single snapshot of the environment variables.
+    ///
+    /// Unlike [`Envconfig::init_from_env`], which reads every variable separately, the environment
+    /// is read once and all fields, including the ones of nested structures, are resolved against it.
+    /// This guarantees a consistent view even if the environment is changed concurrently.
+    ///
+    /// # Errors
+    /// - Environment variable is missing.
+    /// - Failed to parse environment variable.
+    /// - Environment variable is not valid Unicode.
+    fn init_from_env_snapshot() -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        Self::init_from_hashmap(&env_snapshot_for::<Self>()?)
+    }
+
+    /// Initialize structure from environment variables, or report all problems and exit.
+    ///
+    /// Meant for the start of binaries: if the config cannot be loaded, every missing or invalid
+    /// variable (see [`Envconfig::collect_errors`]) is printed to stderr together with the doc
+    /// comment of its field, and the process exits with code 78 (`EX_CONFIG` of `sysexits.h`).
+    /// The output is colored if stderr is a terminal and `NO_COLOR` is not set.
+    /// The environment is read once.
+    #[must_use]
+    fn init_from_env_or_exit() -> Self
+    where
+        Self: Sized,
+    {
+        let source = match env_snapshot_for::<Self>() {
+            Ok(source) => source,
+            Err(err) => exit_with_errors(&Self::struct_info(), &[err]),
+        };
+        match Self::init_from_hashmap(&source) {
+            Ok(config) => config,
+            Err(err) => {
+                let mut errors = Self::collect_errors(&source);
+                if errors.is_empty() {
+                    errors.push(err);
+                }
+                exit_with_errors(&Self::struct_info(), &errors)
+            }
+        }
+    }
+
+    /// Loads every field from a hashmap on its own and returns all errors, instead of only the first one.
+    ///
+    /// Fields of nested structures are checked too. Of a config enum, only the config of the
+    /// selected variant is checked. Returns an empty list if the structure can be initialized.
+    ///
+    /// The default implementation returns the error of [`Envconfig::init_from_hashmap`].
+    #[must_use]
+    fn collect_errors(hashmap: &HashMap<String, String>) -> Vec<Error>
+    where
+        Self: Sized,
+    {
+        Self::init_from_hashmap(hashmap).err().into_iter().collect()
+    }
+
+    /// Initialize structure from environment variables, rejecting unknown variables with the given prefix.
+    ///
+    /// Every variable starting with `prefix` must be consumed by a field (including fields of
+    /// nested structures and of all variants of config enums), so typos like `MYAPP_DB_HSOT`
+    /// are reported instead of silently ignored. The environment is read once.
+    ///
+    /// # Errors
+    /// - A variable with the prefix is not declared, see [`Error::UnknownVars`].
+    /// - Environment variable is missing.
+    /// - Failed to parse environment variable.
+    /// - Environment variable is not valid Unicode.
+    fn init_from_env_strict(prefix: &str) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        Self::init_from_hashmap_strict(&env_snapshot_for::<Self>()?, prefix)
+    }
+
+    /// Initialize structure from a hashmap, rejecting unknown keys with the given prefix.
+    ///
+    /// See [`Envconfig::init_from_env_strict`].
+    ///
+    /// # Errors
+    /// - A key with the prefix is not declared, see [`Error::UnknownVars`].
+    /// - Environment variable is missing.
+    /// - Failed to parse environment variable.
+    fn init_from_hashmap_strict(
+        hashmap: &HashMap<String, String>,
+        prefix: &str,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        check_known_vars(
+            hashmap
+                .keys()
+                .map(String::as_str)
+                .filter(|name| name.starts_with(prefix)),
+            &Self::struct_info().var_names(),
+        )?;
+        Self::init_from_hashmap(hashmap)
+    }
+
     /// Initialize structure from a hashmap.
     ///
     /// # Errors
@@ -34,4 +141,177 @@ pub trait Envconfig {
     fn init_from_hashmap(hashmap: &HashMap<String, String>) -> Result<Self, Error>
     where
         Self: Sized;
+
+    /// Initialize structure from key/value pairs, e.g. a `BTreeMap`, a `HashMap<&str, &str>`,
+    /// a `HashMap` with a custom hasher or an array of tuples.
+    ///
+    /// ```
+    /// # use envconfig::Envconfig;
+    /// # use std::collections::BTreeMap;
+    /// #[derive(Envconfig)]
+    /// struct Config {
+    ///     #[envconfig(from = "HTTP_PORT")]
+    ///     http_port: u16,
+    /// }
+    ///
+    /// let config = Config::init_from_iter([("HTTP_PORT", "8080")]).unwrap();
+    /// assert_eq!(config.http_port, 8080);
+    ///
+    /// let vars = BTreeMap::from([("HTTP_PORT".to_string(), "9090".to_string())]);
+    /// let config = Config::init_from_iter(&vars).unwrap();
+    /// assert_eq!(config.http_port, 9090);
+    /// ```
+    ///
+    /// If a key occurs more than once, the last value is used.
+    ///
+    /// # Errors
+    /// - Environment variable is missing.
+    /// - Failed to parse environment variable.
+    fn init_from_iter<I, K, V>(iter: I) -> Result<Self, Error>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let hashmap = iter
+            .into_iter()
+            .map(|(key, value)| (key.as_ref().to_string(), value.as_ref().to_string()))
+            .collect::<HashMap<_, _>>();
+        Self::init_from_hashmap(&hashmap)
+    }
+
+    /// Initialize structure from environment variables and report where each value came from.
+    ///
+    /// The environment is read once, so the report always matches the returned config.
+    /// Values of secret variables are redacted in the report.
+    ///
+    /// # Errors
+    /// - Environment variable is missing.
+    /// - Failed to parse environment variable.
+    /// - Environment variable is not valid Unicode.
+    fn init_from_env_with_report() -> Result<(Self, Report), Error>
+    where
+        Self: Sized,
+    {
+        let source = env_snapshot_for::<Self>()?;
+        let config = Self::init_from_hashmap(&source)?;
+        let report = Report::new(&Self::struct_info(), &source, Origin::Environment);
+        Ok((config, report))
+    }
+
+    /// Initialize structure from a hashmap and report where each value came from.
+    ///
+    /// Values of secret variables are redacted in the report.
+    ///
+    /// # Errors
+    /// - Environment variable is missing.
+    /// - Failed to parse environment variable.
+    fn init_from_hashmap_with_report(
+        hashmap: &HashMap<String, String>,
+    ) -> Result<(Self, Report), Error>
+    where
+        Self: Sized,
+    {
+        let config = Self::init_from_hashmap(hashmap)?;
+        let report = Report::new(&Self::struct_info(), hashmap, Origin::HashMap);
+        Ok((config, report))
+    }
+
+    /// Checks that every string default (`#[envconfig(default = "...")]`) can be parsed,
+    /// including the defaults of nested structures.
+    ///
+    /// Call it from a test to catch invalid defaults before they are needed in production:
+    ///
+    /// ```
+    /// # use envconfig::Envconfig;
+    /// #[derive(Envconfig)]
+    /// struct Config {
+    ///     #[envconfig(from = "HTTP_PORT", default = "8080")]
+    ///     http_port: u16,
+    /// }
+    ///
+    /// // e.g. in a `#[test]` function
+    /// Config::check_defaults().unwrap();
+    /// ```
+    ///
+    /// The default implementation does not check anything.
+    ///
+    /// # Errors
+    /// - A default value cannot be parsed into the field type.
+    fn check_defaults() -> Result<(), Error>
+    where
+        Self: Sized,
+    {
+        Ok(())
+    }
+
+    /// Describes the structure: its fields, their variables, defaults and doc comments.
+    ///
+    /// The default implementation describes a structure without fields.
+    #[must_use]
+    fn struct_info() -> StructInfo
+    where
+        Self: Sized,
+    {
+        StructInfo {
+            name: std::any::type_name::<Self>(),
+            doc: None,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Lists every variable the structure is loaded from, flattened through nested structures.
+    ///
+    /// Each [`EnvVar`] carries the path of the field, e.g. `db.host`, and a [`VarInfo`](crate::VarInfo)
+    /// with the variable name, the Rust type, the default and whether the variable is required,
+    /// e.g. for documentation, health endpoints or tests:
+    ///
+    /// ```
+    /// # use envconfig::Envconfig;
+    /// #[derive(Envconfig)]
+    /// struct DbConfig {
+    ///     #[envconfig(from = "DB_HOST")]
+    ///     host: String,
+    /// }
+    ///
+    /// #[derive(Envconfig)]
+    /// struct Config {
+    ///     #[envconfig(nested)]
+    ///     db: DbConfig,
+    ///     #[envconfig(from = "HTTP_PORT", default = "8080")]
+    ///     http_port: u16,
+    /// }
+    ///
+    /// let vars = Config::env_vars();
+    /// let names = vars.iter().map(|var| var.info.name).collect::<Vec<_>>();
+    /// assert_eq!(names, ["DB_HOST", "HTTP_PORT"]);
+    /// let paths = vars.iter().map(|var| var.path.as_str()).collect::<Vec<_>>();
+    /// assert_eq!(paths, ["db.host", "http_port"]);
+    /// ```
+    ///
+    /// Variables of all variants of config enums are included. See [`StructInfo::vars`].
+    #[must_use]
+    fn env_vars() -> Vec<EnvVar>
+    where
+        Self: Sized,
+    {
+        Self::struct_info().vars()
+    }
+
+    /// Generates a [JSON Schema](https://json-schema.org/) describing the structure.
+    ///
+    /// Every field becomes a property named after the field. Properties of variables
+    /// carry the variable name in `x-env-var` and the Rust type in `x-rust-type`,
+    /// secret variables are marked as `writeOnly`. Nested structs become nested objects.
+    ///
+    /// Requires the `json-schema` feature.
+    #[cfg(feature = "json-schema")]
+    #[must_use]
+    fn json_schema() -> serde_json::Value
+    where
+        Self: Sized,
+    {
+        crate::schema::json_schema(&Self::struct_info())
+    }
 }
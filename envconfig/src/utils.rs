@@ -1,7 +1,11 @@
 use std::env;
 use std::str::FromStr;
+use std::time::Duration;
 
-use crate::error::Error;
+use crate::error::{Error, InvalidValueError};
+use crate::meta::{FieldInfo, StructInfo, VarInfo};
+use crate::suggest::similar_names;
+use crate::traits::Envconfig;
 use std::collections::HashMap;
 
 /// Load an environment variable by name and parse it into type `T`.
@@ -12,19 +16,10 @@ use std::collections::HashMap;
 /// - Environment variable is not present
 /// - Parsing failed
 pub fn load_var<T: FromStr, S: ::std::hash::BuildHasher>(
-    var_name: &'static str,
+    var_name: &str,
     hashmap: Option<&HashMap<String, String, S>>,
 ) -> Result<T, Error> {
-    match hashmap {
-        None => env::var(var_name).ok(),
-        Some(hashmap) => hashmap.get(var_name).map(std::string::ToString::to_string),
-    }
-    .ok_or(Error::EnvVarMissing { name: var_name })
-    .and_then(|string_value| {
-        string_value
-            .parse::<T>()
-            .map_err(|_| Error::ParseError { name: var_name })
-    })
+    load_and_parse(var_name, hashmap, parse_from_str)
 }
 
 /// Tries to load an environment variable by name and parse it into type `T`.
@@ -35,23 +30,11 @@ pub fn load_var<T: FromStr, S: ::std::hash::BuildHasher>(
 /// # Errors
 /// - Parsing failed
 pub fn load_var_with_default<T: FromStr, S: ::std::hash::BuildHasher>(
-    var_name: &'static str,
+    var_name: &str,
     hashmap: Option<&HashMap<String, String, S>>,
     default: &'static str,
 ) -> Result<T, Error> {
-    let opt_var = match hashmap {
-        None => env::var(var_name).ok(),
-        Some(hashmap) => hashmap.get(var_name).map(std::string::ToString::to_string),
-    };
-
-    let string_value = match opt_var {
-        None => default,
-        Some(ref value) => value,
-    };
-
-    string_value
-        .parse::<T>()
-        .map_err(|_| Error::ParseError { name: var_name })
+    load_and_parse_with_default(var_name, hashmap, default, parse_from_str)
 }
 
 /// Tries to load an environment variable by name and parse it into type `T`.
@@ -62,19 +45,538 @@ pub fn load_var_with_default<T: FromStr, S: ::std::hash::BuildHasher>(
 /// # Errors
 /// - Parsing failed
 pub fn load_optional_var<T: FromStr, S: ::std::hash::BuildHasher>(
-    var_name: &'static str,
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<Option<T>, Error> {
+    load_and_parse_optional(var_name, hashmap, parse_from_str)
+}
+
+/// Load an environment variable by name and parse it into type `T`, a `#[derive(EnvconfigValue)]` enum.
+///
+/// Unlike [`load_var`], a value that is not accepted is reported together with the accepted values.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Environment variable is not present
+/// - Value is not one of the accepted values
+pub fn load_value_var<T: FromStr<Err = InvalidValueError>, S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<T, Error> {
+    load_and_parse(var_name, hashmap, parse_value)
+}
+
+/// Tries to load an environment variable by name and parse it into type `T`, a `#[derive(EnvconfigValue)]` enum.
+/// If the environment variable is not present, it parses the default value.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Value is not one of the accepted values
+pub fn load_value_var_with_default<
+    T: FromStr<Err = InvalidValueError>,
+    S: ::std::hash::BuildHasher,
+>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+    default: &'static str,
+) -> Result<T, Error> {
+    load_and_parse_with_default(var_name, hashmap, default, parse_value)
+}
+
+/// Tries to load an environment variable by name and parse it into type `T`, a `#[derive(EnvconfigValue)]` enum.
+/// If the environment variable is not present, it returns `None`.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Value is not one of the accepted values
+pub fn load_optional_value_var<T: FromStr<Err = InvalidValueError>, S: ::std::hash::BuildHasher>(
+    var_name: &str,
     hashmap: Option<&HashMap<String, String, S>>,
 ) -> Result<Option<T>, Error> {
-    let opt_var = match hashmap {
-        None => env::var(var_name).ok(),
-        Some(hashmap) => hashmap.get(var_name).map(std::string::ToString::to_string),
+    load_and_parse_optional(var_name, hashmap, parse_value)
+}
+
+/// Load an environment variable by name and deserialize its JSON value into type `T`.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Environment variable is not present
+/// - Deserialization failed
+#[cfg(feature = "serde")]
+pub fn load_json_var<T: serde::de::DeserializeOwned, S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<T, Error> {
+    load_and_parse(var_name, hashmap, parse_json)
+}
+
+/// Tries to load an environment variable by name and deserialize its JSON value into type `T`.
+/// If the environment variable is not present, it deserializes the default value.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Deserialization failed
+#[cfg(feature = "serde")]
+pub fn load_json_var_with_default<T: serde::de::DeserializeOwned, S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+    default: &'static str,
+) -> Result<T, Error> {
+    load_and_parse_with_default(var_name, hashmap, default, parse_json)
+}
+
+/// Tries to load an environment variable by name and deserialize its JSON value into type `T`.
+/// If the environment variable is not present, it returns `None`.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Deserialization failed
+#[cfg(feature = "serde")]
+pub fn load_optional_json_var<T: serde::de::DeserializeOwned, S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<Option<T>, Error> {
+    load_and_parse_optional(var_name, hashmap, parse_json)
+}
+
+/// Load an environment variable by name and parse it leniently into a `bool`.
+///
+/// Accepted values (case-insensitive): `true`, `1`, `yes`, `y`, `on` and `false`, `0`, `no`, `n`, `off`.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Environment variable is not present
+/// - Value is not one of the accepted values
+pub fn load_bool_var<S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<bool, Error> {
+    load_and_parse(var_name, hashmap, parse_bool)
+}
+
+/// Tries to load an environment variable by name and parse it leniently into a `bool`.
+/// If the environment variable is not present, it parses the default value.
+///
+/// See [`load_bool_var`] for the accepted values.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Value is not one of the accepted values
+pub fn load_bool_var_with_default<S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+    default: &'static str,
+) -> Result<bool, Error> {
+    load_and_parse_with_default(var_name, hashmap, default, parse_bool)
+}
+
+/// Tries to load an environment variable by name and parse it leniently into a `bool`.
+/// If the environment variable is not present, it returns `None`.
+///
+/// See [`load_bool_var`] for the accepted values.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Value is not one of the accepted values
+pub fn load_optional_bool_var<S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<Option<bool>, Error> {
+    load_and_parse_optional(var_name, hashmap, parse_bool)
+}
+
+/// Load an environment variable by name and parse it into a [`Duration`].
+///
+/// The value is a sequence of integers with units, e.g. `30s`, `5m`, `1h30m` or `250ms`.
+/// Supported units are `ns`, `us`, `ms`, `s`, `m`, `h` and `d`.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Environment variable is not present
+/// - Value is not a valid duration
+pub fn load_duration_var<S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<Duration, Error> {
+    load_and_parse(var_name, hashmap, parse_duration)
+}
+
+/// Tries to load an environment variable by name and parse it into a [`Duration`].
+/// If the environment variable is not present, it parses the default value.
+///
+/// See [`load_duration_var`] for the format.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Value is not a valid duration
+pub fn load_duration_var_with_default<S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+    default: &'static str,
+) -> Result<Duration, Error> {
+    load_and_parse_with_default(var_name, hashmap, default, parse_duration)
+}
+
+/// Tries to load an environment variable by name and parse it into a [`Duration`].
+/// If the environment variable is not present, it returns `None`.
+///
+/// See [`load_duration_var`] for the format.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Value is not a valid duration
+pub fn load_optional_duration_var<S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<Option<Duration>, Error> {
+    load_and_parse_optional(var_name, hashmap, parse_duration)
+}
+
+/// Load an environment variable by name and parse it as a byte size into integer type `T`.
+///
+/// The value is an integer with an optional unit, e.g. `512`, `64MiB` or `1GB`.
+/// Decimal (`KB`, `MB`, `GB`, `TB`, `PB`) and binary (`KiB`, `MiB`, `GiB`, `TiB`, `PiB`) units
+/// are supported; units are case-insensitive and a missing unit means bytes.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Environment variable is not present
+/// - Value is not a valid byte size or does not fit into `T`
+pub fn load_bytes_var<T: TryFrom<u64>, S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<T, Error> {
+    load_and_parse(var_name, hashmap, parse_bytes)
+}
+
+/// Tries to load an environment variable by name and parse it as a byte size into integer type `T`.
+/// If the environment variable is not present, it parses the default value.
+///
+/// See [`load_bytes_var`] for the format.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Value is not a valid byte size or does not fit into `T`
+pub fn load_bytes_var_with_default<T: TryFrom<u64>, S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+    default: &'static str,
+) -> Result<T, Error> {
+    load_and_parse_with_default(var_name, hashmap, default, parse_bytes)
+}
+
+/// Tries to load an environment variable by name and parse it as a byte size into integer type `T`.
+/// If the environment variable is not present, it returns `None`.
+///
+/// See [`load_bytes_var`] for the format.
+///
+/// This function can also use a hashmap as a fallback or for testing purposes.
+///
+/// # Errors
+/// - Value is not a valid byte size or does not fit into `T`
+pub fn load_optional_bytes_var<T: TryFrom<u64>, S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<Option<T>, Error> {
+    load_and_parse_optional(var_name, hashmap, parse_bytes)
+}
+
+/// Collects all environment variables with valid UTF-8 names and values.
+pub(crate) fn env_snapshot() -> HashMap<String, String> {
+    env::vars_os()
+        .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+        .collect()
+}
+
+/// Collects the environment variables to load `C` from, see [`env_snapshot`].
+///
+/// Values that are not valid Unicode cannot be part of the snapshot. They are recorded while
+/// reading the environment, and a variable of `C` with such a value is reported as
+/// [`Error::DecodeError`] like [`Envconfig::init_from_env`] does, instead of as missing.
+///
+/// # Errors
+/// - The value of a variable of `C` is not valid Unicode
+pub(crate) fn env_snapshot_for<C: Envconfig>() -> Result<HashMap<String, String>, Error> {
+    let mut snapshot = HashMap::new();
+    let mut undecodable = Vec::new();
+    for (key, value) in env::vars_os() {
+        let Ok(key) = key.into_string() else {
+            continue;
+        };
+        match value.into_string() {
+            Ok(value) => {
+                snapshot.insert(key, value);
+            }
+            Err(_) => undecodable.push(key),
+        }
+    }
+    match find_undecodable(&C::struct_info(), &undecodable) {
+        Some(err) => Err(err),
+        None => Ok(snapshot),
+    }
+}
+
+/// Returns the [`Error::DecodeError`] of the first variable of `info` named in `undecodable`,
+/// with the path of its field as built by the generated code.
+fn find_undecodable(info: &StructInfo, undecodable: &[String]) -> Option<Error> {
+    let decode_error = |var: &VarInfo| {
+        undecodable
+            .iter()
+            .any(|name| name == var.name)
+            .then(|| Error::DecodeError {
+                name: var.name.to_string(),
+            })
+    };
+    info.fields.iter().find_map(|field| match field {
+        FieldInfo::Var(var) => decode_error(var),
+        FieldInfo::Nested { field, info, .. } => {
+            find_undecodable(info, undecodable).map(|err| err.in_field(field, info))
+        }
+        FieldInfo::Variants { var, variants } => decode_error(var).or_else(|| {
+            variants.iter().find_map(|variant| {
+                let info = variant.info.as_ref()?;
+                find_undecodable(info, undecodable).map(|err| err.in_field(variant.value, info))
+            })
+        }),
+    })
+}
+
+/// Looks up the raw value of a variable either in the environment or in the hashmap.
+///
+/// # Errors
+/// - The value in the environment is not valid Unicode
+pub(crate) fn lookup<S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Result<Option<String>, Error> {
+    match hashmap {
+        None => match env::var(var_name) {
+            Ok(value) => Ok(Some(value)),
+            Err(env::VarError::NotPresent) => Ok(None),
+            Err(env::VarError::NotUnicode(_)) => Err(Error::DecodeError {
+                name: var_name.to_string(),
+            }),
+        },
+        Some(hashmap) => Ok(hashmap.get(var_name).map(std::string::ToString::to_string)),
+    }
+}
+
+fn load_and_parse<T, S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+    parse: impl FnOnce(&str, &str) -> Result<T, Error>,
+) -> Result<T, Error> {
+    lookup(var_name, hashmap)?
+        .ok_or_else(|| missing_var_error(var_name, hashmap))
+        .and_then(|string_value| parse(var_name, &string_value))
+}
+
+/// Builds the error for a missing variable, suggesting similar names present in the source.
+fn missing_var_error<S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+) -> Error {
+    let suggestions = match hashmap {
+        None => {
+            let env = env_snapshot();
+            similar_names(var_name, env.keys().map(String::as_str))
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        }
+        Some(hashmap) => similar_names(var_name, hashmap.keys().map(String::as_str))
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+    };
+    Error::EnvVarMissing {
+        name: var_name.to_string(),
+        suggestions,
+    }
+}
+
+fn load_and_parse_with_default<T, S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+    default: &'static str,
+    parse: impl FnOnce(&str, &str) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let opt_var = lookup(var_name, hashmap)?;
+
+    let string_value = match opt_var {
+        None => default,
+        Some(ref value) => value,
     };
 
-    match opt_var {
+    parse(var_name, string_value)
+}
+
+fn load_and_parse_optional<T, S: ::std::hash::BuildHasher>(
+    var_name: &str,
+    hashmap: Option<&HashMap<String, String, S>>,
+    parse: impl FnOnce(&str, &str) -> Result<T, Error>,
+) -> Result<Option<T>, Error> {
+    match lookup(var_name, hashmap)? {
         None => Ok(None),
-        Some(string_value) => string_value
-            .parse::<T>()
-            .map(Some)
-            .map_err(|_| Error::ParseError { name: var_name }),
+        Some(string_value) => parse(var_name, &string_value).map(Some),
     }
 }
+
+fn parse_from_str<T: FromStr>(var_name: &str, value: &str) -> Result<T, Error> {
+    value.parse::<T>().map_err(|_| Error::ParseError {
+        name: var_name.to_string(),
+    })
+}
+
+fn parse_value<T: FromStr<Err = InvalidValueError>>(
+    var_name: &str,
+    value: &str,
+) -> Result<T, Error> {
+    value.parse::<T>().map_err(|err| Error::InvalidValue {
+        name: var_name.to_string(),
+        value: value.to_string(),
+        message: err.to_string(),
+    })
+}
+
+const TRUTHY: [&str; 5] = ["true", "1", "yes", "y", "on"];
+const FALSY: [&str; 5] = ["false", "0", "no", "n", "off"];
+
+fn parse_bool(var_name: &str, value: &str) -> Result<bool, Error> {
+    let trimmed = value.trim();
+    if TRUTHY.iter().any(|v| v.eq_ignore_ascii_case(trimmed)) {
+        Ok(true)
+    } else if FALSY.iter().any(|v| v.eq_ignore_ascii_case(trimmed)) {
+        Ok(false)
+    } else {
+        Err(Error::InvalidValue {
+            name: var_name.to_string(),
+            value: value.to_string(),
+            message: format!(
+                "expected one of: {}, {}",
+                TRUTHY.join(", "),
+                FALSY.join(", ")
+            ),
+        })
+    }
+}
+
+fn parse_duration(var_name: &str, value: &str) -> Result<Duration, Error> {
+    let invalid = |message: &str| Error::InvalidValue {
+        name: var_name.to_string(),
+        value: value.to_string(),
+        message: message.to_string(),
+    };
+
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return Err(invalid("expected a duration like `30s`, `5m` or `1h30m`"));
+    }
+
+    let mut nanos: u128 = 0;
+    while !rest.is_empty() {
+        let digits_end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let number = rest[..digits_end]
+            .parse::<u128>()
+            .map_err(|_| invalid("expected a duration like `30s`, `5m` or `1h30m`"))?;
+        rest = rest[digits_end..].trim_start();
+
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit() || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let unit_nanos: u128 = match &rest[..unit_end] {
+            "ns" => 1,
+            "us" | "µs" => 1_000,
+            "ms" => 1_000_000,
+            "s" => 1_000_000_000,
+            "m" => 60 * 1_000_000_000,
+            "h" => 60 * 60 * 1_000_000_000,
+            "d" => 24 * 60 * 60 * 1_000_000_000,
+            "" => {
+                return Err(invalid(
+                    "missing unit, expected one of: ns, us, ms, s, m, h, d",
+                ))
+            }
+            _ => {
+                return Err(invalid(
+                    "unknown unit, expected one of: ns, us, ms, s, m, h, d",
+                ))
+            }
+        };
+        rest = rest[unit_end..].trim_start();
+
+        nanos = number
+            .checked_mul(unit_nanos)
+            .and_then(|component| nanos.checked_add(component))
+            .ok_or_else(|| invalid("duration is too large"))?;
+    }
+
+    let secs =
+        u64::try_from(nanos / 1_000_000_000).map_err(|_| invalid("duration is too large"))?;
+    // The remainder is always below one second, so it fits into `u32`
+    let subsec_nanos = u32::try_from(nanos % 1_000_000_000).unwrap_or_default();
+    Ok(Duration::new(secs, subsec_nanos))
+}
+
+fn parse_bytes<T: TryFrom<u64>>(var_name: &str, value: &str) -> Result<T, Error> {
+    let invalid = |message: &str| Error::InvalidValue {
+        name: var_name.to_string(),
+        value: value.to_string(),
+        message: message.to_string(),
+    };
+
+    let trimmed = value.trim();
+    let digits_end = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    let number = trimmed[..digits_end]
+        .parse::<u64>()
+        .map_err(|_| invalid("expected a byte size like `512`, `64MiB` or `1GB`"))?;
+    let multiplier: u64 = match trimmed[digits_end..].trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1_000,
+        "m" | "mb" => 1_000_000,
+        "g" | "gb" => 1_000_000_000,
+        "t" | "tb" => 1_000_000_000_000,
+        "p" | "pb" => 1_000_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        "pib" => 1 << 50,
+        _ => {
+            return Err(invalid(
+                "unknown unit, expected one of: B, KB, MB, GB, TB, PB, KiB, MiB, GiB, TiB, PiB",
+            ))
+        }
+    };
+
+    let bytes = number
+        .checked_mul(multiplier)
+        .ok_or_else(|| invalid("byte size is too large"))?;
+    T::try_from(bytes).map_err(|_| invalid("byte size does not fit into the field type"))
+}
+
+#[cfg(feature = "serde")]
+fn parse_json<T: serde::de::DeserializeOwned>(var_name: &str, value: &str) -> Result<T, Error> {
+    serde_json::from_str(value).map_err(|err| Error::DeserializeError {
+        name: var_name.to_string(),
+        message: err.to_string(),
+    })
+}
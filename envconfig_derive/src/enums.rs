@@ -0,0 +1,333 @@
+//! Derive of `Envconfig` for enums whose variant is selected by a discriminator variable.
+
+use quote::quote;
+use syn::{DataEnum, DeriveInput, Fields, Lit, Variant};
+
+use crate::{
+    fetch_args_from_attr, fetch_envconfig_attr, find_item_in_list, gen_doc, MatchingItem, Source,
+};
+
+/// Parsed `#[envconfig(...)]` attribute of an enum
+struct EnumAttrs {
+    /// Name of the discriminator variable, taken from `from` or derived from the enum name
+    from: String,
+    /// `default`: variant used when the discriminator is not set
+    default: Option<String>,
+}
+
+/// A variant of the enum together with its discriminator value
+struct VariantConfig<'a> {
+    variant: &'a Variant,
+    /// Value of the discriminator selecting the variant, taken from `rename` or derived from the variant name
+    value: String,
+}
+
+/// Generates the `impl Envconfig` block for the provided enum
+///
+/// # Panics
+/// - A variant is neither a unit variant nor has exactly one unnamed field
+/// - The default is not a value of any variant
+pub(crate) fn impl_envconfig_for_enum(
+    input: &DeriveInput,
+    data: &DataEnum,
+) -> proc_macro2::TokenStream {
+    let enum_name = &input.ident;
+    let enum_name_str = enum_name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let attrs = parse_enum_attrs(input);
+    let variants = data.variants.iter().map(parse_variant).collect::<Vec<_>>();
+
+    let values = variants
+        .iter()
+        .map(|v| v.value.as_str())
+        .collect::<Vec<_>>();
+    if let Some(default) = &attrs.default {
+        assert!(
+            values
+                .iter()
+                .any(|value| value.eq_ignore_ascii_case(default)),
+            "Default `{default}` of enum `{enum_name}` is not one of: {}",
+            values.join(", ")
+        );
+    }
+
+    let init_from_env = gen_init(&attrs, &variants, &Source::Environment);
+    let init_from_hashmap = gen_init(&attrs, &variants, &Source::HashMap);
+    let collect_errors = gen_collect_errors(&attrs, &variants);
+    let (variant_configs, variant_values): (Vec<_>, Vec<_>) = variants
+        .iter()
+        .filter_map(|v| variant_config_type(v.variant).map(|ty| (ty, v.value.as_str())))
+        .unzip();
+    let variant_infos = variants.iter().map(gen_variant_info);
+    let doc = gen_doc(&input.attrs);
+    let from = &attrs.from;
+    let field = to_snake_case(&enum_name_str);
+    let (default, required) = if let Some(default) = &attrs.default {
+        (quote! { ::core::option::Option::Some(#default) }, false)
+    } else {
+        (quote! { ::core::option::Option::None }, true)
+    };
+
+    quote! {
+        impl #impl_generics Envconfig for #enum_name #ty_generics #where_clause {
+            fn init_from_env() -> ::std::result::Result<Self, ::envconfig::Error> {
+                #init_from_env
+            }
+
+            fn init_from_hashmap(hashmap: &::std::collections::HashMap<String, String>) -> ::std::result::Result<Self, ::envconfig::Error> {
+                #init_from_hashmap
+            }
+
+            #[deprecated(since="0.10.0", note="Please use `::init_from_env` instead")]
+            fn init() -> ::std::result::Result<Self, ::envconfig::Error> {
+                Self::init_from_env()
+            }
+
+            fn collect_errors(hashmap: &::std::collections::HashMap<String, String>) -> ::std::vec::Vec<::envconfig::Error> {
+                #collect_errors
+            }
+
+            fn check_defaults() -> ::std::result::Result<(), ::envconfig::Error> {
+                #(
+                    <#variant_configs as ::envconfig::Envconfig>::check_defaults()
+                        .map_err(|err| err.in_field(#variant_values, &<#variant_configs as ::envconfig::Envconfig>::struct_info()))?;
+                )*
+                ::core::result::Result::Ok(())
+            }
+
+            fn struct_info() -> ::envconfig::StructInfo {
+                ::envconfig::StructInfo {
+                    name: #enum_name_str,
+                    doc: #doc,
+                    fields: ::std::vec![
+                        ::envconfig::FieldInfo::Variants {
+                            var: ::envconfig::VarInfo {
+                                name: #from,
+                                field: #field,
+                                type_name: "String",
+                                default: #default,
+                                required: #required,
+                                struct_default: false,
+                                secret: false,
+                                format: ::envconfig::ValueFormat::FromStr,
+                                doc: #doc,
+                            },
+                            variants: ::std::vec![#(#variant_infos,)*],
+                        },
+                    ],
+                }
+            }
+        }
+    }
+}
+
+/// Parses the `envconfig` attribute of the provided enum
+fn parse_enum_attrs(input: &DeriveInput) -> EnumAttrs {
+    let derived_from = to_snake_case(&input.ident.to_string()).to_uppercase();
+    let Some(attr) = fetch_envconfig_attr(&input.attrs) else {
+        return EnumAttrs {
+            from: derived_from,
+            default: None,
+        };
+    };
+    let list = fetch_args_from_attr(&format!("enum `{}`", input.ident), attr);
+
+    let from = match find_item_in_list(&list, "from") {
+        Some(MatchingItem::WithValue(Lit::Str(v))) => v.value(),
+        Some(_) => panic!("`from` attribute must have a string value"),
+        None => derived_from,
+    };
+    let default = match find_item_in_list(&list, "default") {
+        Some(MatchingItem::WithValue(Lit::Str(v))) => Some(v.value()),
+        Some(_) => panic!("`default` attribute of an enum must have a string value"),
+        None => None,
+    };
+
+    EnumAttrs { from, default }
+}
+
+/// Parses the `envconfig` attribute of the provided variant
+///
+/// # Panics
+/// Panics if the variant is neither a unit variant nor has exactly one unnamed field
+fn parse_variant(variant: &Variant) -> VariantConfig<'_> {
+    let is_supported = match &variant.fields {
+        Fields::Unit => true,
+        Fields::Unnamed(fields) => fields.unnamed.len() == 1,
+        Fields::Named(_) => false,
+    };
+    assert!(
+        is_supported,
+        "Variant `{}` must be a unit variant or contain exactly one config, e.g. `{}(Config)`",
+        variant.ident, variant.ident
+    );
+
+    VariantConfig {
+        variant,
+        value: variant_value(variant),
+    }
+}
+
+/// Returns the value selecting the provided variant, taken from `rename` or derived from the variant name
+pub(crate) fn variant_value(variant: &Variant) -> String {
+    let derived_value = to_snake_case(&variant.ident.to_string());
+    match fetch_envconfig_attr(&variant.attrs) {
+        Some(attr) => {
+            let list = fetch_args_from_attr(&format!("variant `{}`", variant.ident), attr);
+            match find_item_in_list(&list, "rename") {
+                Some(MatchingItem::WithValue(Lit::Str(v))) => v.value(),
+                Some(_) => panic!("`rename` attribute must have a string value"),
+                None => derived_value,
+            }
+        }
+        None => derived_value,
+    }
+}
+
+/// Returns the config type of a variant with a single unnamed field
+fn variant_config_type(variant: &Variant) -> Option<&syn::Type> {
+    match &variant.fields {
+        Fields::Unnamed(fields) => fields.unnamed.first().map(|field| &field.ty),
+        _ => None,
+    }
+}
+
+/// Generates the body of `init_from_env()` or `init_from_hashmap()`
+fn gen_init(
+    attrs: &EnumAttrs,
+    variants: &[VariantConfig],
+    source: &Source,
+) -> proc_macro2::TokenStream {
+    let from = &attrs.from;
+    let (hashmap, init_fn) = match source {
+        Source::Environment => (quote! { None }, quote! { init_from_env() }),
+        Source::HashMap => (
+            quote! { Some(hashmap) },
+            quote! { init_from_hashmap(hashmap) },
+        ),
+    };
+    let load_discriminator = gen_load_discriminator(attrs, &hashmap);
+
+    let values = variants
+        .iter()
+        .map(|v| v.value.as_str())
+        .collect::<Vec<_>>();
+    let branches = variants.iter().map(|v| {
+        let value = &v.value;
+        let ident = &v.variant.ident;
+        let construct = if let Some(ty) = variant_config_type(v.variant) {
+            quote! {
+                Self::#ident(
+                    <#ty as ::envconfig::Envconfig>::#init_fn
+                        .map_err(|err| err.in_field(#value, &<#ty as ::envconfig::Envconfig>::struct_info()))?
+                )
+            }
+        } else {
+            quote! { Self::#ident }
+        };
+        quote! {
+            if value.eq_ignore_ascii_case(#value) {
+                return ::core::result::Result::Ok(#construct);
+            }
+        }
+    });
+
+    quote! {
+        let value = #load_discriminator?;
+        #(#branches)*
+        ::core::result::Result::Err(::envconfig::Error::InvalidVariant {
+            name: ::std::string::ToString::to_string(#from),
+            value,
+            choices: ::std::vec![#(#values),*],
+        })
+    }
+}
+
+/// Generates the loading of the discriminator variable, resulting in `Result<String, Error>`
+fn gen_load_discriminator(
+    attrs: &EnumAttrs,
+    hashmap: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let from = &attrs.from;
+    if let Some(default) = &attrs.default {
+        quote! {
+            ::envconfig::load_var_with_default::<::std::string::String, ::std::collections::hash_map::RandomState>(#from, #hashmap, #default)
+        }
+    } else {
+        quote! {
+            ::envconfig::load_var::<::std::string::String, ::std::collections::hash_map::RandomState>(#from, #hashmap)
+        }
+    }
+}
+
+/// Generates the body of `collect_errors()`
+///
+/// Errors of the selected variant's config are collected from its fields,
+/// any other error is the error of `init_from_hashmap()`.
+fn gen_collect_errors(attrs: &EnumAttrs, variants: &[VariantConfig]) -> proc_macro2::TokenStream {
+    let load_discriminator = gen_load_discriminator(attrs, &quote! { Some(hashmap) });
+    let branches = variants.iter().filter_map(|v| {
+        let value = &v.value;
+        let ty = variant_config_type(v.variant)?;
+        Some(quote! {
+            if value.eq_ignore_ascii_case(#value) {
+                return <#ty as ::envconfig::Envconfig>::collect_errors(hashmap)
+                    .into_iter()
+                    .map(|err| err.in_field(#value, &<#ty as ::envconfig::Envconfig>::struct_info()))
+                    .collect();
+            }
+        })
+    });
+
+    quote! {
+        match #load_discriminator {
+            ::core::result::Result::Ok(value) => {
+                #(#branches)*
+                Self::init_from_hashmap(hashmap).err().into_iter().collect()
+            }
+            ::core::result::Result::Err(err) => ::std::vec![err],
+        }
+    }
+}
+
+/// Generates the [`envconfig::VariantInfo`] describing the provided variant
+fn gen_variant_info(variant: &VariantConfig) -> proc_macro2::TokenStream {
+    let name = variant.variant.ident.to_string();
+    let value = &variant.value;
+    let doc = gen_doc(&variant.variant.attrs);
+    let info = if let Some(ty) = variant_config_type(variant.variant) {
+        quote! {
+            ::core::option::Option::Some(<#ty as ::envconfig::Envconfig>::struct_info())
+        }
+    } else {
+        quote! { ::core::option::Option::None }
+    };
+    quote! {
+        ::envconfig::VariantInfo {
+            name: #name,
+            value: #value,
+            doc: #doc,
+            info: #info,
+        }
+    }
+}
+
+/// Converts a `CamelCase` name into `snake_case`, e.g. `InMemory` into `in_memory`
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
@@ -1,9 +1,12 @@
-//! Provides a derive macro that implements `Envconfig` trait.
+//! Provides derive macros that implement `Envconfig` trait and `FromStr` for config values.
+
+mod enums;
+mod value;
 
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{
-    punctuated::Punctuated, token::Comma, Attribute, Data::Struct, DeriveInput, Expr, Field,
+    punctuated::Punctuated, token::Comma, Attribute, Data, Data::Struct, DeriveInput, Expr, Field,
     Fields, Ident, Lit, Meta, MetaNameValue, Token,
 };
 
@@ -11,7 +14,7 @@ use syn::{
 ///
 /// # Panics
 /// - The provided [`TokenStream`] cannot be parsed
-/// - The provided input is not a named struct
+/// - The provided input is not a named struct or an enum
 /// - Invalid configuration in the `envconfig` attributes
 #[proc_macro_derive(Envconfig, attributes(envconfig))]
 pub fn derive(input: TokenStream) -> TokenStream {
@@ -20,6 +23,22 @@ pub fn derive(input: TokenStream) -> TokenStream {
     gen.into()
 }
 
+/// Custom derive of [`std::str::FromStr`] for unit enums used as values of variables
+///
+/// Variants are matched case-insensitively against their `snake_case` names,
+/// which can be changed with `#[envconfig(rename = "...")]`.
+///
+/// # Panics
+/// - The provided [`TokenStream`] cannot be parsed
+/// - The provided input is not an enum with unit variants only
+/// - Invalid configuration in the `envconfig` attributes
+#[proc_macro_derive(EnvconfigValue, attributes(envconfig))]
+pub fn derive_value(input: TokenStream) -> TokenStream {
+    let derive_input: DeriveInput = syn::parse(input).unwrap();
+    let gen = value::impl_envconfig_value(&derive_input);
+    gen.into()
+}
+
 /// Source type for envconfig variables.
 ///
 /// - `Environment`: Environment variables.
@@ -29,44 +48,165 @@ enum Source {
     HashMap,
 }
 
-/// Wrapper for [`impl_envconfig_for_struct`].
+/// Format of a variable value.
+///
+/// - `FromStr`: Parsed with [`std::str::FromStr`].
+/// - `Json`: Deserialized from JSON with `serde_json` (`#[envconfig(json)]`).
+/// - `Bool`: Parsed leniently as a boolean (`bool` and `Option<bool>` fields).
+/// - `Duration`: Parsed from a human-readable duration like `1h30m` (`#[envconfig(duration)]`).
+/// - `Bytes`: Parsed from a byte size like `64MiB` into an integer (`#[envconfig(bytes)]`).
+/// - `Value`: Parsed into an `EnvconfigValue` enum, errors list the accepted values (`#[envconfig(value)]`).
+enum Format {
+    FromStr,
+    Json,
+    Bool,
+    Duration,
+    Bytes,
+    Value,
+}
+
+/// Kind of `load_*` function used for a variable.
 ///
-/// Checks if the provided input is a struct and generates the desired `impl EnvConfig`
+/// - `Required`: The variable must be set (`load_var`).
+/// - `WithDefault`: A string default is parsed if the variable is not set (`load_var_with_default`).
+/// - `Optional`: `None` is returned if the variable is not set (`load_optional_var`).
+enum Loader {
+    Required,
+    WithDefault,
+    Optional,
+}
+
+impl Format {
+    /// Returns the variant of `envconfig::ValueFormat` describing this format
+    fn value_format(&self) -> Ident {
+        let name = match self {
+            Format::FromStr => "FromStr",
+            Format::Json => "Json",
+            Format::Bool => "Bool",
+            Format::Duration => "Duration",
+            Format::Bytes => "Bytes",
+            Format::Value => "Value",
+        };
+        Ident::new(name, proc_macro2::Span::call_site())
+    }
+
+    /// Returns the path of the `envconfig::load_*` function loading a variable of this format into `ty`
+    fn load_fn(&self, loader: &Loader, ty: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let format = match self {
+            Format::FromStr => "",
+            Format::Json => "json_",
+            Format::Bool => "bool_",
+            Format::Duration => "duration_",
+            Format::Bytes => "bytes_",
+            Format::Value => "value_",
+        };
+        let name = match loader {
+            Loader::Required => format!("load_{format}var"),
+            Loader::WithDefault => format!("load_{format}var_with_default"),
+            Loader::Optional => format!("load_optional_{format}var"),
+        };
+        let ident = Ident::new(&name, proc_macro2::Span::call_site());
+        match self {
+            Format::FromStr | Format::Json | Format::Bytes | Format::Value => {
+                quote! { ::envconfig::#ident::<#ty, ::std::collections::hash_map::RandomState> }
+            }
+            Format::Bool | Format::Duration => {
+                quote! { ::envconfig::#ident::<::std::collections::hash_map::RandomState> }
+            }
+        }
+    }
+}
+
+/// Parsed `#[envconfig(...)]` attribute of a struct
+struct StructAttrs {
+    /// `default`: unset fields are taken from the `Default` implementation of the struct
+    default: bool,
+    /// `interpolate`: `${VAR}` references are expanded in values of all fields
+    interpolate: bool,
+}
+
+/// Parses the `envconfig` attribute of the provided struct
 ///
 /// # Panics
-/// Panics if `input.data` isn't a struct
-fn impl_envconfig(input: &DeriveInput) -> proc_macro2::TokenStream {
-    let struct_name = &input.ident;
+/// Panics if the attribute contains invalid items
+fn parse_struct_attrs(input: &DeriveInput) -> StructAttrs {
+    let Some(attr) = fetch_envconfig_attr(&input.attrs) else {
+        return StructAttrs {
+            default: false,
+            interpolate: false,
+        };
+    };
+    let list = fetch_args_from_attr(&format!("struct `{}`", input.ident), attr);
 
+    StructAttrs {
+        default: find_flag_in_list(&list, "default"),
+        interpolate: find_flag_in_list(&list, "interpolate"),
+    }
+}
+
+/// Wrapper for [`impl_envconfig_for_struct`] and [`enums::impl_envconfig_for_enum`].
+///
+/// Checks if the provided input is a struct or an enum and generates the desired `impl EnvConfig`
+///
+/// # Panics
+/// Panics if `input.data` isn't a struct with named fields or an enum
+fn impl_envconfig(input: &DeriveInput) -> proc_macro2::TokenStream {
     // Check if derive input is a struct and contains named fields. Panic otherwise
     let named_fields = match input.data {
         Struct(ref ds) => match ds.fields {
             Fields::Named(ref fields) => &fields.named,
             _ => panic!("envconfig supports only named fields"),
         },
-        _ => panic!("envconfig only supports non-tuple structs"),
+        Data::Enum(ref data) => return enums::impl_envconfig_for_enum(input, data),
+        Data::Union(_) => panic!("envconfig only supports non-tuple structs and enums"),
     };
 
-    let inner_impl = impl_envconfig_for_struct(struct_name, named_fields);
+    let struct_attrs = parse_struct_attrs(input);
+    let inner_impl = impl_envconfig_for_struct(input, &struct_attrs, named_fields);
 
     quote!(#inner_impl)
 }
 
 /// Generates the `impl Envconfig` blocks for the provided struct
 fn impl_envconfig_for_struct(
-    struct_name: &Ident,
+    input: &DeriveInput,
+    struct_attrs: &StructAttrs,
     fields: &Punctuated<Field, Comma>,
 ) -> proc_macro2::TokenStream {
     let field_assigns_env = fields
         .iter()
-        .map(|field| gen_field_assign(field, &Source::Environment));
+        .map(|field| gen_field_assign(field, struct_attrs, &Source::Environment));
     let field_assigns_hashmap = fields
         .iter()
-        .map(|field| gen_field_assign(field, &Source::HashMap));
+        .map(|field| gen_field_assign(field, struct_attrs, &Source::HashMap));
+    let field_infos = fields
+        .iter()
+        .filter_map(|field| gen_field_info(field, struct_attrs));
+    let default_checks = fields
+        .iter()
+        .map(|field| gen_default_check(field, struct_attrs));
+    let error_collects = fields
+        .iter()
+        .map(|field| gen_error_collect(field, struct_attrs));
+    let struct_name = &input.ident;
+    let struct_name_str = struct_name.to_string();
+    let struct_doc = gen_doc(&input.attrs);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // With `#[envconfig(default)]` on the struct, unset fields are moved out of the default value
+    let defaults = if struct_attrs.default {
+        quote! {
+            #[allow(unused_variables)]
+            let defaults = <Self as ::core::default::Default>::default();
+        }
+    } else {
+        quote! {}
+    };
 
     quote! {
-        impl Envconfig for #struct_name {
+        impl #impl_generics Envconfig for #struct_name #ty_generics #where_clause {
             fn init_from_env() -> ::std::result::Result<Self, ::envconfig::Error> {
+                #defaults
                 let config = Self {
                     #(#field_assigns_env,)*
                 };
@@ -74,6 +214,7 @@ fn impl_envconfig_for_struct(
             }
 
             fn init_from_hashmap(hashmap: &::std::collections::HashMap<String, String>) -> ::std::result::Result<Self, ::envconfig::Error> {
+                #defaults
                 let config = Self {
                     #(#field_assigns_hashmap,)*
                 };
@@ -84,50 +225,534 @@ fn impl_envconfig_for_struct(
             fn init() -> ::std::result::Result<Self, ::envconfig::Error> {
                 Self::init_from_env()
             }
+
+            fn collect_errors(hashmap: &::std::collections::HashMap<String, String>) -> ::std::vec::Vec<::envconfig::Error> {
+                #defaults
+                let mut errors = ::std::vec::Vec::new();
+                #(#error_collects)*
+                errors
+            }
+
+            fn check_defaults() -> ::std::result::Result<(), ::envconfig::Error> {
+                let hashmap = ::std::collections::HashMap::<String, String>::new();
+                #(#default_checks)*
+                ::core::result::Result::Ok(())
+            }
+
+            fn struct_info() -> ::envconfig::StructInfo {
+                ::envconfig::StructInfo {
+                    name: #struct_name_str,
+                    doc: #struct_doc,
+                    fields: ::std::vec![#(#field_infos,)*],
+                }
+            }
         }
     }
 }
 
-/// Generates the field assignments for the config struct
-fn gen_field_assign(field: &Field, source: &Source) -> proc_macro2::TokenStream {
-    let attr = fetch_envconfig_attr_from_field(field);
-
-    if let Some(attr) = attr {
-        // if #[envconfig(...)] is there
-        let list = fetch_args_from_attr(field, attr);
-
-        // If nested attribute is present
-        let nested_value_opt = find_item_in_list(&list, "nested");
-        match nested_value_opt {
-            Some(MatchingItem::NoValue) => return gen_field_assign_for_struct_type(field, source),
-            Some(MatchingItem::WithValue(_)) => {
-                panic!("`nested` attribute must not have a value")
+/// Default value of a field
+enum DefaultValue {
+    /// `default = "..."`: parsed at runtime like a value of the variable
+    Str(Lit),
+    /// `default = 8080`, `default_fn = "..."` or `default_expr = "..."`: an expression of the field type
+    Expr {
+        tokens: proc_macro2::TokenStream,
+        /// The value as a string, if it is a literal
+        repr: Option<String>,
+    },
+    /// `#[envconfig(default)]` on the struct: the field of the struct's `Default` value
+    Struct(proc_macro2::TokenStream),
+}
+
+/// Parsed `#[envconfig(...)]` attribute of a field
+#[allow(clippy::struct_excessive_bools)]
+struct FieldAttrs {
+    /// `nested`: the field is a config struct itself
+    nested: bool,
+    /// `skip`: the field is not loaded, but initialized from its default
+    skip: bool,
+    /// Name of the environment variable, taken from `from` or derived from the field name
+    from: proc_macro2::TokenStream,
+    /// `default`, `default_fn` or `default_expr`: value used when the variable is not set
+    default: Option<DefaultValue>,
+    /// `json`, `bytes`, `duration` or `value`: format of the value
+    format: Format,
+    /// `secret`: the value must not be exposed
+    secret: bool,
+    /// `interpolate`: `${VAR}` references are expanded in the value
+    interpolate: bool,
+}
+
+/// Parses the `envconfig` attribute of the provided field
+///
+/// # Panics
+/// Panics if the attribute contains invalid items
+fn parse_field_attrs(field: &Field) -> FieldAttrs {
+    let Some(attr) = fetch_envconfig_attr_from_field(field) else {
+        // if #[envconfig(...)] is not present
+        // use field name as name of the environment variable
+        return FieldAttrs {
+            nested: false,
+            skip: false,
+            from: field_to_env_var_name(field),
+            default: None,
+            format: default_format(field),
+            secret: false,
+            interpolate: false,
+        };
+    };
+
+    // if #[envconfig(...)] is there
+    let list = fetch_args_from_attr(&format!("field `{}`", field_name(field)), attr);
+
+    // If nested attribute is present
+    let nested = find_flag_in_list(&list, "nested");
+
+    // If skip attribute is present
+    let skip = find_flag_in_list(&list, "skip");
+    assert!(
+        !(skip && nested),
+        "`skip` and `nested` attributes cannot be used together on field `{}`",
+        field_name(field)
+    );
+
+    // Default value for the field
+    let default = parse_default(&list);
+
+    // Format of the value
+    let formats = [
+        ("json", Format::Json),
+        ("bytes", Format::Bytes),
+        ("duration", Format::Duration),
+        ("value", Format::Value),
+    ]
+    .into_iter()
+    .filter(|(name, _)| find_flag_in_list(&list, name))
+    .map(|(_, format)| format)
+    .collect::<Vec<_>>();
+    assert!(
+        formats.len() <= 1,
+        "Only one of `json`, `bytes`, `duration` and `value` attributes can be used on field `{}`",
+        field_name(field)
+    );
+    let format = formats
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| default_format(field));
+
+    // Environment variable name
+    let from = match find_item_in_list(&list, "from") {
+        Some(MatchingItem::WithValue(v)) => quote! { #v },
+        Some(MatchingItem::NoValue) => panic!("`from` attribute must have a value"),
+        None => field_to_env_var_name(field),
+    };
+
+    let secret = find_flag_in_list(&list, "secret");
+    let interpolate = find_flag_in_list(&list, "interpolate");
+
+    FieldAttrs {
+        nested,
+        skip,
+        from,
+        default,
+        format,
+        secret,
+        interpolate,
+    }
+}
+
+/// Returns the format of a field without a format attribute
+///
+/// `bool` fields (optionally wrapped in `Option`) are parsed leniently, other fields with [`std::str::FromStr`].
+fn default_format(field: &Field) -> Format {
+    let ty = to_s(&field.ty);
+    let ty = ty
+        .strip_prefix("Option < ")
+        .and_then(|inner| inner.strip_suffix(" >"))
+        .unwrap_or(&ty);
+    if ty == "bool" {
+        Format::Bool
+    } else {
+        Format::FromStr
+    }
+}
+
+/// Parses the `default`, `default_fn` and `default_expr` items
+///
+/// # Panics
+/// - More than one of the items is present
+/// - The item has no value, or the value is not a valid path or expression
+fn parse_default(list: &[Meta]) -> Option<DefaultValue> {
+    let items = ["default", "default_fn", "default_expr"]
+        .into_iter()
+        .filter_map(|name| find_meta_in_list(list, name).map(|meta| (name, meta)))
+        .collect::<Vec<_>>();
+    assert!(
+        items.len() <= 1,
+        "Only one of `default`, `default_fn` and `default_expr` attributes can be used"
+    );
+    let (name, meta) = items.into_iter().next()?;
+
+    // Bare `default`: use `Default::default()` of the field type
+    if name == "default" && matches!(meta, Meta::Path(_)) {
+        return Some(DefaultValue::Expr {
+            tokens: quote! { ::core::default::Default::default() },
+            repr: None,
+        });
+    }
+
+    let Meta::NameValue(MetaNameValue { value, .. }) = meta else {
+        panic!("`{name}` attribute must have a value")
+    };
+
+    let default = match (name, value) {
+        (
+            "default",
+            Expr::Lit(syn::ExprLit {
+                lit: str_lit @ Lit::Str(_),
+                ..
+            }),
+        ) => DefaultValue::Str(str_lit.clone()),
+        ("default", expr) => DefaultValue::Expr {
+            tokens: quote! { #expr },
+            repr: expr_to_string(expr),
+        },
+        (
+            _,
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(value),
+                ..
+            }),
+        ) => {
+            if name == "default_fn" {
+                let path = value
+                    .parse::<syn::Path>()
+                    .unwrap_or_else(|err| panic!("Invalid path in `default_fn`: {err}"));
+                DefaultValue::Expr {
+                    tokens: quote! { #path() },
+                    repr: None,
+                }
+            } else {
+                let expr = value
+                    .parse::<Expr>()
+                    .unwrap_or_else(|err| panic!("Invalid expression in `default_expr`: {err}"));
+                DefaultValue::Expr {
+                    tokens: quote! { #expr },
+                    repr: None,
+                }
             }
-            None => {}
         }
+        _ => panic!("`{name}` attribute must be a string literal"),
+    };
+    Some(default)
+}
+
+/// Renders a literal (optionally negated) as a string for [`envconfig::VarInfo`]
+///
+/// Returns `None` for other expressions, as they are computed at runtime.
+fn expr_to_string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Str(lit) => Some(lit.value()),
+            Lit::Int(lit) => Some(lit.base10_digits().to_string()),
+            Lit::Float(lit) => Some(lit.base10_digits().to_string()),
+            Lit::Bool(lit) => Some(lit.value.to_string()),
+            Lit::Char(lit) => Some(lit.value().to_string()),
+            _ => None,
+        },
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => expr_to_string(expr).map(|value| format!("-{value}")),
+        _ => None,
+    }
+}
+
+/// Generates the field assignments for the config struct
+fn gen_field_assign(
+    field: &Field,
+    struct_attrs: &StructAttrs,
+    source: &Source,
+) -> proc_macro2::TokenStream {
+    let ident = &field.ident;
+    let value = gen_field_value(field, struct_attrs, source);
+    quote! {
+        #ident: #value
+    }
+}
+
+/// Generates the expression loading the value of the provided field
+fn gen_field_value(
+    field: &Field,
+    struct_attrs: &StructAttrs,
+    source: &Source,
+) -> proc_macro2::TokenStream {
+    let attrs = parse_field_attrs(field);
+
+    if attrs.skip {
+        gen_field_assign_for_skipped_field(field, attrs.default, struct_attrs)
+    } else if attrs.nested {
+        gen_field_assign_for_struct_type(field, source)
+    } else {
+        let default = attrs
+            .default
+            .or_else(|| struct_default(field, struct_attrs));
+        let hashmap = if attrs.interpolate || struct_attrs.interpolate {
+            gen_interpolated_source(&attrs.from, default.as_ref(), source)
+        } else {
+            hashmap_arg(source)
+        };
+        let value = gen(
+            field,
+            &attrs.from,
+            default.as_ref(),
+            &attrs.format,
+            &hashmap,
+        );
+        if attrs.secret {
+            gen_redacted(field, &value)
+        } else {
+            value
+        }
+    }
+}
+
+/// Wraps the value of a secret field, so its errors do not expose the value
+fn gen_redacted(field: &Field, value: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let ty = &field.ty;
+    quote! {
+        (|| -> ::std::result::Result<#ty, ::envconfig::Error> {
+            let value: #ty = #value;
+            ::core::result::Result::Ok(value)
+        })()
+        .map_err(::envconfig::Error::redacted)?
+    }
+}
+
+/// Generates the collection of the errors of the provided field for `collect_errors()`
+///
+/// The field is loaded on its own, so errors of other fields do not hide its errors.
+/// Skipped fields cannot fail, so nothing is generated for them.
+///
+/// # Panics
+/// Panics if a nested field type is not a path
+fn gen_error_collect(field: &Field, struct_attrs: &StructAttrs) -> proc_macro2::TokenStream {
+    let attrs = parse_field_attrs(field);
+
+    if attrs.skip {
+        return quote! {};
+    }
+
+    if attrs.nested {
+        let syn::Type::Path(path) = &field.ty else {
+            panic!("Expected field type to be a path: {}", field_name(field))
+        };
+        let name = field_name(field);
+        return quote! {
+            errors.extend(
+                <#path as ::envconfig::Envconfig>::collect_errors(hashmap)
+                    .into_iter()
+                    .map(|err| err.in_field(#name, &<#path as ::envconfig::Envconfig>::struct_info())),
+            );
+        };
+    }
+
+    let ty = &field.ty;
+    let value = gen_field_value(field, struct_attrs, &Source::HashMap);
+    quote! {
+        let result = (|| -> ::std::result::Result<(), ::envconfig::Error> {
+            let _: #ty = #value;
+            ::core::result::Result::Ok(())
+        })();
+        if let ::core::result::Result::Err(err) = result {
+            errors.push(err);
+        }
+    }
+}
+
+/// Generates the value of a field marked with `#[envconfig(skip)]`
+///
+/// The field is initialized from `default_fn`, `default_expr` or a typed `default` if given,
+/// from the struct's `Default` value with `#[envconfig(default)]` on the struct,
+/// or from `Default::default()` of the field type otherwise.
+///
+/// # Panics
+/// Panics if a string default is given, as there is no variable to parse it like
+fn gen_field_assign_for_skipped_field(
+    field: &Field,
+    default: Option<DefaultValue>,
+    struct_attrs: &StructAttrs,
+) -> proc_macro2::TokenStream {
+    match default.or_else(|| struct_default(field, struct_attrs)) {
+        Some(DefaultValue::Expr { tokens, .. } | DefaultValue::Struct(tokens)) => tokens,
+        Some(DefaultValue::Str(_)) => panic!(
+            "Skipped field `{}` cannot have a string default, use `default_fn` or `default_expr` instead",
+            field_name(field)
+        ),
+        None => quote! { ::core::default::Default::default() },
+    }
+}
+
+/// Returns the default taken from the struct's `Default` value, if `#[envconfig(default)]` is set on the struct
+fn struct_default(field: &Field, struct_attrs: &StructAttrs) -> Option<DefaultValue> {
+    let ident = &field.ident;
+    struct_attrs
+        .default
+        .then(|| DefaultValue::Struct(quote! { defaults.#ident }))
+}
+
+/// Generates the check of the string default of the provided field for `check_defaults()`
+///
+/// The default is parsed the same way as it is parsed at runtime when the variable is not set.
+/// Typed defaults are checked by the compiler, so nothing is generated for them.
+///
+/// # Panics
+/// Panics if a nested field type is not a path
+fn gen_default_check(field: &Field, struct_attrs: &StructAttrs) -> proc_macro2::TokenStream {
+    let attrs = parse_field_attrs(field);
 
-        // Default value for the field
-        let opt_default = match find_item_in_list(&list, "default") {
-            Some(MatchingItem::WithValue(v)) => Some(v),
-            Some(MatchingItem::NoValue) => panic!("`default` attribute must have a value"),
-            None => None,
+    if attrs.skip {
+        return quote! {};
+    }
+
+    if attrs.nested {
+        let syn::Type::Path(path) = &field.ty else {
+            panic!("Expected field type to be a path: {}", field_name(field))
         };
+        let name = field_name(field);
+        return quote! {
+            <#path as ::envconfig::Envconfig>::check_defaults()
+                .map_err(|err| err.in_field(#name, &<#path as ::envconfig::Envconfig>::struct_info()))?;
+        };
+    }
+
+    let Some(DefaultValue::Str(default)) = &attrs.default else {
+        return quote! {};
+    };
+    // Defaults with references depend on other variables, so they cannot be checked on their own
+    let interpolate = attrs.interpolate || struct_attrs.interpolate;
+    if interpolate && to_s(default).contains('$') {
+        return quote! {};
+    }
+    let from = &attrs.from;
+    let ty = &field.ty;
+    let load_fn = attrs.format.load_fn(&Loader::WithDefault, &quote! { #ty });
+    quote! {
+        #load_fn(#from, Some(&hashmap), #default)
+            .map_err(|_| ::envconfig::Error::InvalidDefault {
+                name: ::std::string::ToString::to_string(#from),
+            })?;
+    }
+}
+
+/// Generates the [`envconfig::FieldInfo`] describing the provided field
+///
+/// Returns `None` for skipped fields, as they are not loaded.
+///
+/// # Panics
+/// Panics if a nested field type is not a path
+fn gen_field_info(field: &Field, struct_attrs: &StructAttrs) -> Option<proc_macro2::TokenStream> {
+    let attrs = parse_field_attrs(field);
+    let field_name = field_name(field);
+    let doc = gen_doc(&field.attrs);
+
+    if attrs.skip {
+        return None;
+    }
 
-        // Environment variable name
-        let from_opt = find_item_in_list(&list, "from");
-        let env_var = match from_opt {
-            Some(MatchingItem::WithValue(v)) => quote! { #v },
-            Some(MatchingItem::NoValue) => panic!("`from` attribute must have a value"),
-            None => field_to_env_var_name(field),
+    if attrs.nested {
+        let syn::Type::Path(path) = &field.ty else {
+            panic!("Expected field type to be a path: {field_name}")
         };
+        return Some(quote! {
+            ::envconfig::FieldInfo::Nested {
+                field: #field_name,
+                doc: #doc,
+                info: <#path as ::envconfig::Envconfig>::struct_info(),
+            }
+        });
+    }
+
+    let from = &attrs.from;
+    let is_optional = to_s(&field.ty).starts_with("Option ");
+    let type_name = type_name(&field.ty);
+    let default = match &attrs.default {
+        Some(DefaultValue::Str(lit)) => quote! { ::core::option::Option::Some(#lit) },
+        Some(DefaultValue::Expr {
+            repr: Some(repr), ..
+        }) => quote! { ::core::option::Option::Some(#repr) },
+        Some(DefaultValue::Expr { repr: None, .. } | DefaultValue::Struct(_)) | None => {
+            quote! { ::core::option::Option::None }
+        }
+    };
+    let struct_default = attrs.default.is_none() && struct_attrs.default;
+    let required = !is_optional && attrs.default.is_none() && !struct_attrs.default;
+    let secret = attrs.secret;
+    let format = attrs.format.value_format();
+
+    Some(quote! {
+        ::envconfig::FieldInfo::Var(::envconfig::VarInfo {
+            name: #from,
+            field: #field_name,
+            type_name: #type_name,
+            default: #default,
+            required: #required,
+            struct_default: #struct_default,
+            secret: #secret,
+            format: ::envconfig::ValueFormat::#format,
+            doc: #doc,
+        })
+    })
+}
+
+/// Collects the doc comment from the provided attributes into an `Option<&'static str>`
+fn gen_doc(attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(syn::ExprLit {
+                        lit: Lit::Str(line),
+                        ..
+                    }),
+                ..
+            }) => Some(line.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect::<Vec<_>>();
 
-        gen(field, &env_var, opt_default, source)
+    if lines.is_empty() {
+        quote! { ::core::option::Option::None }
     } else {
-        // if #[envconfig(...)] is not present
-        // use field name as name of the environment variable
-        let env_var = field_to_env_var_name(field);
-        gen(field, &env_var, None, source)
+        let doc = lines.join("\n");
+        quote! { ::core::option::Option::Some(#doc) }
+    }
+}
+
+/// Renders the provided type as a compact string, e.g. `Option<u16>`
+fn type_name(ty: &syn::Type) -> String {
+    let tokens = to_s(ty);
+    let chars = tokens.chars().collect::<Vec<_>>();
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut name = String::with_capacity(tokens.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' {
+            let prev = chars[i - 1];
+            let next = chars[i + 1];
+            if !(is_word(prev) && is_word(next)) {
+                continue;
+            }
+        }
+        name.push(c);
+        if c == ',' {
+            name.push(' ');
+        }
     }
+    name
 }
 
 /// Turns the field name into an uppercase [`proc_macro2::TokenStream`]
@@ -139,97 +764,144 @@ fn field_to_env_var_name(field: &Field) -> proc_macro2::TokenStream {
     quote! { #field_name }
 }
 
-/// Generates the derived field assignment for the provided field
+/// Generates the derived value for the provided field
+///
+/// `hashmap` is the `hashmap` argument passed to the `load_*` functions.
 fn gen(
     field: &Field,
     from: &proc_macro2::TokenStream,
-    opt_default: Option<&Lit>,
-    source: &Source,
+    opt_default: Option<&DefaultValue>,
+    format: &Format,
+    hashmap: &proc_macro2::TokenStream,
 ) -> proc_macro2::TokenStream {
     let field_type = &field.ty;
     if to_s(field_type).starts_with("Option ") {
-        gen_field_assign_for_optional_type(field, from, opt_default, source)
+        gen_field_assign_for_optional_type(field, from, opt_default, format, hashmap)
     } else {
-        gen_field_assign_for_non_optional_type(field, from, opt_default, source)
+        gen_field_assign_for_non_optional_type(from, opt_default, format, hashmap)
     }
 }
 
-/// Generates the derived field assignment for a (nested) struct type
+/// Generates the `hashmap` argument for a field with `interpolate`
+///
+/// The value (or the string default, if the variable is not set) is expanded by
+/// `envconfig::interpolate_var` into a source holding only this variable.
+fn gen_interpolated_source(
+    from: &proc_macro2::TokenStream,
+    opt_default: Option<&DefaultValue>,
+    source: &Source,
+) -> proc_macro2::TokenStream {
+    let hashmap = hashmap_arg(source);
+    let default = if let Some(DefaultValue::Str(default)) = opt_default {
+        quote! { ::core::option::Option::Some(#default) }
+    } else {
+        quote! { ::core::option::Option::None }
+    };
+    quote! {
+        Some(&::envconfig::interpolate_var::<::std::collections::hash_map::RandomState>(#from, #hashmap, #default)?)
+    }
+}
+
+/// Generates the derived field value for a (nested) struct type
+///
+/// Errors of the nested struct are marked with the field name, see `envconfig::Error::Nested`.
 ///
 /// # Panics
 /// Panics if the field type is not a path
 fn gen_field_assign_for_struct_type(field: &Field, source: &Source) -> proc_macro2::TokenStream {
     let ident: &Option<Ident> = &field.ident;
-    match &field.ty {
-        syn::Type::Path(path) => match source {
-            Source::Environment => quote! {
-                #ident: #path :: init_from_env()?
-            },
-            Source::HashMap => quote! {
-                #ident: #path :: init_from_hashmap(hashmap)?
-            },
-        },
-        _ => panic!("Expected field type to be a path: {ident:?}",),
+    let name = field_name(field);
+    let syn::Type::Path(path) = &field.ty else {
+        panic!("Expected field type to be a path: {ident:?}")
+    };
+    let init = match source {
+        Source::Environment => quote! { init_from_env() },
+        Source::HashMap => quote! { init_from_hashmap(hashmap) },
+    };
+    quote! {
+        <#path as ::envconfig::Envconfig>::#init
+            .map_err(|err| err.in_field(#name, &<#path as ::envconfig::Envconfig>::struct_info()))?
     }
 }
 
-/// Generates the derived field assignment for an optional type
+/// Generates the derived field value for an optional type
 ///
 /// # Panics
 /// Panics if the field is an optional type with a default value
 fn gen_field_assign_for_optional_type(
     field: &Field,
     from: &proc_macro2::TokenStream,
-    opt_default: Option<&Lit>,
-    source: &Source,
+    opt_default: Option<&DefaultValue>,
+    format: &Format,
+    hashmap: &proc_macro2::TokenStream,
 ) -> proc_macro2::TokenStream {
     let field_name = &field.ident;
 
-    assert!(opt_default.is_none(), "Optional type on field `{}` with default value does not make sense and therefore is not allowed", to_s(field_name));
+    let load_fn = format.load_fn(&Loader::Optional, &quote! { _ });
 
-    match source {
-        Source::Environment => quote! {
-            #field_name: ::envconfig::load_optional_var::<_,::std::collections::hash_map::RandomState>(#from, None)?
+    match opt_default {
+        Some(DefaultValue::Struct(tokens)) => quote! {
+            match #load_fn(#from, #hashmap)? {
+                ::core::option::Option::Some(value) => ::core::option::Option::Some(value),
+                ::core::option::Option::None => #tokens,
+            }
         },
-        Source::HashMap => quote! {
-            #field_name: ::envconfig::load_optional_var::<_,::std::collections::hash_map::RandomState>(#from, Some(hashmap))?
+        Some(_) => panic!("Optional type on field `{}` with default value does not make sense and therefore is not allowed", to_s(field_name)),
+        None => quote! {
+            #load_fn(#from, #hashmap)?
         },
     }
 }
 
-/// Generates the derived field assignment for non-optional types
+/// Generates the derived field value for non-optional types
 fn gen_field_assign_for_non_optional_type(
-    field: &Field,
     from: &proc_macro2::TokenStream,
-    opt_default: Option<&Lit>,
-    source: &Source,
+    opt_default: Option<&DefaultValue>,
+    format: &Format,
+    hashmap: &proc_macro2::TokenStream,
 ) -> proc_macro2::TokenStream {
-    let field_name = &field.ident;
-
-    if let Some(default) = opt_default {
-        match source {
-            Source::Environment => quote! {
-                #field_name: ::envconfig::load_var_with_default::<_,::std::collections::hash_map::RandomState>(#from, None, #default)?
-            },
-            Source::HashMap => quote! {
-                #field_name: ::envconfig::load_var_with_default::<_,::std::collections::hash_map::RandomState>(#from, Some(hashmap), #default)?
-            },
+    match opt_default {
+        Some(DefaultValue::Str(default)) => {
+            let load_fn = format.load_fn(&Loader::WithDefault, &quote! { _ });
+            quote! {
+                #load_fn(#from, #hashmap, #default)?
+            }
         }
-    } else {
-        match source {
-            Source::Environment => quote! {
-                #field_name: ::envconfig::load_var::<_,::std::collections::hash_map::RandomState>(#from, None)?
-            },
-            Source::HashMap => quote! {
-                #field_name: ::envconfig::load_var::<_,::std::collections::hash_map::RandomState>(#from, Some(hashmap))?
-            },
+        Some(DefaultValue::Expr { tokens, .. } | DefaultValue::Struct(tokens)) => {
+            // Typed default: it is evaluated only if the variable is not set
+            let load_fn = format.load_fn(&Loader::Optional, &quote! { _ });
+            quote! {
+                match #load_fn(#from, #hashmap)? {
+                    ::core::option::Option::Some(value) => value,
+                    ::core::option::Option::None => #tokens,
+                }
+            }
+        }
+        None => {
+            let load_fn = format.load_fn(&Loader::Required, &quote! { _ });
+            quote! {
+                #load_fn(#from, #hashmap)?
+            }
         }
     }
 }
 
+/// Generates the `hashmap` argument passed to the `load_*` functions
+fn hashmap_arg(source: &Source) -> proc_macro2::TokenStream {
+    match source {
+        Source::Environment => quote! { None },
+        Source::HashMap => quote! { Some(hashmap) },
+    }
+}
+
 /// Tries to get the (first) `envconfig` attribute from the provided field
 fn fetch_envconfig_attr_from_field(field: &Field) -> Option<&Attribute> {
-    field.attrs.iter().find(|a| {
+    fetch_envconfig_attr(&field.attrs)
+}
+
+/// Tries to get the (first) `envconfig` attribute from the provided attributes
+fn fetch_envconfig_attr(attrs: &[Attribute]) -> Option<&Attribute> {
+    attrs.iter().find(|a| {
         let path = &a.path();
         let name = quote!(#path).to_string();
         name == "envconfig"
@@ -238,21 +910,17 @@ fn fetch_envconfig_attr_from_field(field: &Field) -> Option<&Attribute> {
 
 /// Fetches the arguments from the provided attribute
 ///
+/// `target` describes where the attribute is placed, e.g. ``field `port` ``.
+///
 /// # Panics
 /// Panics if the attribute cannot be parsed
-fn fetch_args_from_attr(field: &Field, attr: &Attribute) -> Vec<Meta> {
+fn fetch_args_from_attr(target: &str, attr: &Attribute) -> Vec<Meta> {
     let opt_meta = &attr.meta;
 
     match opt_meta {
         Meta::List(l) => l
             .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
-            .unwrap_or_else(|err| {
-                panic!(
-                    "{:?} in `envconfig` attribute on field `{}`",
-                    err,
-                    field_name(field)
-                )
-            })
+            .unwrap_or_else(|err| panic!("{err:?} in `envconfig` attribute on {target}"))
             .iter()
             .cloned()
             .collect(),
@@ -279,6 +947,27 @@ enum MatchingItem<'a> {
 /// - Multiple items with the same name exist
 /// - The item is not a name-value pair or a path
 fn find_item_in_list<'l>(list: &'l [Meta], item_name: &str) -> Option<MatchingItem<'l>> {
+    let matching_result = find_meta_in_list(list, item_name);
+
+    if let Some(meta) = matching_result {
+        return match meta {
+            Meta::NameValue(MetaNameValue {
+                value: Expr::Lit(value),
+                ..
+            }) => Some(MatchingItem::WithValue(&value.lit)),
+            Meta::Path(_) => Some(MatchingItem::NoValue),
+            _ => panic!("Expected `{item_name}` to be a name-value pair or a path"),
+        };
+    }
+
+    None
+}
+
+/// Tries to find the item with the provided name in the list
+///
+/// # Panics
+/// Panics if multiple items with the same name exist
+fn find_meta_in_list<'l>(list: &'l [Meta], item_name: &str) -> Option<&'l Meta> {
     // Find all items with the provided name
     let matching_items = list
         .iter()
@@ -291,20 +980,21 @@ fn find_item_in_list<'l>(list: &'l [Meta], item_name: &str) -> Option<MatchingIt
         "Found multiple `{item_name}` attributes in `envconfig` attribute",
     );
 
-    let matching_result = matching_items.first();
+    matching_items.first().copied()
+}
 
-    if let Some(meta) = matching_result {
-        return match meta {
-            Meta::NameValue(MetaNameValue {
-                value: Expr::Lit(value),
-                ..
-            }) => Some(MatchingItem::WithValue(&value.lit)),
-            Meta::Path(_) => Some(MatchingItem::NoValue),
-            _ => panic!("Expected `{item_name}` to be a name-value pair or a path"),
-        };
+/// Checks whether a flag (an item without a value) is present in the provided list
+///
+/// # Panics
+/// Panics if the item has a value
+fn find_flag_in_list(list: &[Meta], item_name: &str) -> bool {
+    match find_item_in_list(list, item_name) {
+        Some(MatchingItem::NoValue) => true,
+        Some(MatchingItem::WithValue(_)) => {
+            panic!("`{item_name}` attribute must not have a value")
+        }
+        None => false,
     }
-
-    None
 }
 
 /// Returns the name of the field as a string
@@ -0,0 +1,51 @@
+//! Derive of `FromStr` for unit enums used as values of variables.
+
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+use crate::enums::variant_value;
+
+/// Generates the `impl FromStr` block for the provided enum
+///
+/// # Panics
+/// - The input is not an enum
+/// - A variant is not a unit variant
+pub(crate) fn impl_envconfig_value(input: &DeriveInput) -> proc_macro2::TokenStream {
+    let enum_name = &input.ident;
+    let Data::Enum(ref data) = input.data else {
+        panic!("EnvconfigValue can only be derived for enums, `{enum_name}` is not an enum");
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    for variant in &data.variants {
+        assert!(
+            matches!(variant.fields, Fields::Unit),
+            "EnvconfigValue supports only unit variants, `{enum_name}::{}` has fields",
+            variant.ident
+        );
+    }
+
+    let values = data.variants.iter().map(variant_value).collect::<Vec<_>>();
+    let branches = data.variants.iter().zip(&values).map(|(variant, value)| {
+        let ident = &variant.ident;
+        quote! {
+            if value.eq_ignore_ascii_case(#value) {
+                return ::core::result::Result::Ok(Self::#ident);
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::std::str::FromStr for #enum_name #ty_generics #where_clause {
+            type Err = ::envconfig::InvalidValueError;
+
+            fn from_str(value: &str) -> ::std::result::Result<Self, Self::Err> {
+                #(#branches)*
+                ::core::result::Result::Err(::envconfig::InvalidValueError {
+                    value: value.to_string(),
+                    choices: &[#(#values),*],
+                })
+            }
+        }
+    }
+}
@@ -1,8 +1,8 @@
 extern crate envconfig;
 
+use envconfig::testing::EnvGuard;
 use envconfig::{Envconfig, Error};
 use std::collections::HashMap;
-use std::env;
 
 #[derive(Envconfig)]
 pub struct Config {
@@ -13,17 +13,13 @@ pub struct Config {
     pub db_port: u16,
 }
 
-fn setup() {
-    env::remove_var("DB_HOST");
-    env::remove_var("DB_PORT");
+fn setup() -> EnvGuard {
+    EnvGuard::new().remove("DB_HOST").remove("DB_PORT")
 }
 
 #[test]
 fn test_inits_config_from_env_variables() {
-    setup();
-
-    env::set_var("DB_HOST", "localhost");
-    env::set_var("DB_PORT", "5432");
+    let _env = setup().set("DB_HOST", "localhost").set("DB_PORT", "5432");
 
     let config = Config::init_from_env().unwrap();
     assert_eq!(config.db_host, "localhost");
@@ -32,8 +28,6 @@ fn test_inits_config_from_env_variables() {
 
 #[test]
 fn test_inits_config_from_hashmap() {
-    setup();
-
     let mut hashmap = HashMap::new();
     hashmap.insert("DB_HOST".to_string(), "localhost".to_string());
     hashmap.insert("DB_PORT".to_string(), "5432".to_string());
@@ -45,58 +39,99 @@ fn test_inits_config_from_hashmap() {
 
 #[test]
 fn test_checks_presence_of_env_vars() {
-    setup();
-
-    env::set_var("DB_HOST", "localhost");
+    let _env = setup().set("DB_HOST", "localhost");
 
     let err = Config::init_from_env().err().unwrap();
-    let expected_err = Error::EnvVarMissing { name: "DB_PORT" };
-    assert_eq!(err, expected_err);
+    // The suggestions depend on the variables of the process environment
+    assert!(matches!(
+        err,
+        Error::EnvVarMissing { ref name, .. } if name == "DB_PORT"
+    ));
 }
 
 #[test]
 fn test_checks_presence_of_hashmap_keys() {
-    setup();
+    let mut hashmap = HashMap::new();
+    hashmap.insert("DB_HOST".to_string(), "localhost".to_string());
+
+    let err = Config::init_from_hashmap(&hashmap).err().unwrap();
+    let expected_err = Error::EnvVarMissing {
+        name: "DB_PORT".to_string(),
+        suggestions: vec![],
+    };
+    assert_eq!(err, expected_err);
+}
 
+#[test]
+fn test_suggests_similar_keys() {
     let mut hashmap = HashMap::new();
     hashmap.insert("DB_HOST".to_string(), "localhost".to_string());
+    hashmap.insert("db_port".to_string(), "5432".to_string());
 
     let err = Config::init_from_hashmap(&hashmap).err().unwrap();
-    let expected_err = Error::EnvVarMissing { name: "DB_PORT" };
+    let expected_err = Error::EnvVarMissing {
+        name: "DB_PORT".to_string(),
+        suggestions: vec!["db_port".to_string()],
+    };
     assert_eq!(err, expected_err);
+    assert_eq!(
+        err.to_string(),
+        "Environment variable DB_PORT is missing (did you mean db_port?)"
+    );
 }
 
 #[test]
 fn test_fails_if_can_not_parse_db_port_from_env() {
-    setup();
-
-    env::set_var("DB_HOST", "localhost");
-    env::set_var("DB_PORT", "67000");
+    let _env = setup().set("DB_HOST", "localhost").set("DB_PORT", "67000");
 
     let err = Config::init_from_env().err().unwrap();
-    let expected_err = Error::ParseError { name: "DB_PORT" };
+    let expected_err = Error::ParseError {
+        name: "DB_PORT".to_string(),
+    };
     assert_eq!(err, expected_err);
 }
 
 #[test]
 fn test_fails_if_can_not_parse_db_port_from_hashmap() {
-    setup();
-
     let mut hashmap = HashMap::new();
     hashmap.insert("DB_HOST".to_string(), "localhost".to_string());
     hashmap.insert("DB_PORT".to_string(), "67000".to_string());
 
     let err = Config::init_from_hashmap(&hashmap).err().unwrap();
-    let expected_err = Error::ParseError { name: "DB_PORT" };
+    let expected_err = Error::ParseError {
+        name: "DB_PORT".to_string(),
+    };
+    assert_eq!(err, expected_err);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_fails_if_env_var_is_not_unicode() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let _env = setup()
+        .set("DB_HOST", OsStr::from_bytes(b"local\xffhost"))
+        .set("DB_PORT", "5432");
+
+    let err = Config::init_from_env().err().unwrap();
+    let expected_err = Error::DecodeError {
+        name: "DB_HOST".to_string(),
+    };
     assert_eq!(err, expected_err);
+    assert_eq!(
+        err.to_string(),
+        "Environment variable DB_HOST is not valid Unicode"
+    );
 }
 
 #[test]
+#[allow(clippy::items_after_statements)]
 fn test_custom_from_str() {
     use std::num::ParseIntError;
     use std::str::FromStr;
 
-    setup();
+    let env = EnvGuard::new().set("POINT", "(1,2)");
 
     #[derive(Debug, PartialEq)]
     struct Point {
@@ -129,12 +164,10 @@ fn test_custom_from_str() {
         point: Point,
     }
 
-    env::set_var("POINT", "(1,2)");
-
     let err = Config::init_from_env().unwrap();
     assert_eq!(err.point, Point { x: 1, y: 2 });
 
-    setup();
+    drop(env);
 
     let mut hashmap = HashMap::new();
     hashmap.insert("POINT".to_string(), "(1,2)".to_string());
@@ -142,3 +175,37 @@ fn test_custom_from_str() {
     let err = Config::init_from_hashmap(&hashmap).unwrap();
     assert_eq!(err.point, Point { x: 1, y: 2 });
 }
+
+#[test]
+fn test_custom_from_str_with_any_error_type() {
+    use std::str::FromStr;
+
+    #[derive(Debug, PartialEq)]
+    struct Even(u32);
+
+    impl FromStr for Even {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.parse::<u32>() {
+                Ok(n) if n % 2 == 0 => Ok(Even(n)),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[derive(Envconfig)]
+    pub struct Config {
+        #[envconfig(from = "EVEN")]
+        even: Even,
+    }
+
+    let config = Config::init_from_iter([("EVEN", "4")]).unwrap();
+    assert_eq!(config.even, Even(4));
+
+    let err = Config::init_from_iter([("EVEN", "3")]).err().unwrap();
+    let expected_err = Error::ParseError {
+        name: "EVEN".to_string(),
+    };
+    assert_eq!(err, expected_err);
+}
@@ -0,0 +1,103 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, Error};
+use std::collections::HashMap;
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(from = "DEBUG")]
+    pub debug: bool,
+
+    #[envconfig(from = "VERBOSE", default = "off")]
+    pub verbose: bool,
+
+    #[envconfig(from = "COLOR")]
+    pub color: Option<bool>,
+}
+
+fn config_with_debug(value: &str) -> Result<Config, Error> {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("DEBUG".to_string(), value.to_string());
+    Config::init_from_hashmap(&hashmap)
+}
+
+#[test]
+fn test_parses_truthy_values() {
+    for value in ["true", "TRUE", "1", "yes", "Y", "on", " On "] {
+        assert!(config_with_debug(value).unwrap().debug, "{value}");
+    }
+}
+
+#[test]
+fn test_parses_falsy_values() {
+    for value in ["false", "False", "0", "no", "N", "off", "OFF"] {
+        assert!(!config_with_debug(value).unwrap().debug, "{value}");
+    }
+}
+
+#[test]
+fn test_parses_defaults_and_optional_values() {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("DEBUG".to_string(), "yes".to_string());
+
+    let config = Config::init_from_hashmap(&hashmap).unwrap();
+    assert!(!config.verbose);
+    assert_eq!(config.color, None);
+
+    hashmap.insert("COLOR".to_string(), "on".to_string());
+    let config = Config::init_from_hashmap(&hashmap).unwrap();
+    assert_eq!(config.color, Some(true));
+}
+
+#[test]
+fn test_invalid_value_error() {
+    let err = config_with_debug("maybe").err().unwrap();
+    assert_eq!(
+        err,
+        Error::InvalidValue {
+            name: "DEBUG".to_string(),
+            value: "maybe".to_string(),
+            message: "expected one of: true, 1, yes, y, on, false, 0, no, n, off".to_string(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Invalid value `maybe` of environment variable DEBUG: expected one of: true, 1, yes, y, on, false, 0, no, n, off"
+    );
+}
+
+#[derive(Envconfig)]
+pub struct SecretConfig {
+    #[envconfig(from = "SECRET_FLAG", secret, default = "off")]
+    pub flag: bool,
+
+    #[envconfig(from = "SECRET_PIN", secret)]
+    pub pin: Option<u16>,
+
+    #[envconfig(from = "SECRET_URL", secret, interpolate)]
+    pub url: Option<String>,
+
+    #[envconfig(from = "SECRET_TTL", secret, duration)]
+    pub ttl: Option<std::time::Duration>,
+}
+
+#[test]
+fn test_redacts_invalid_secret_values() {
+    let assert_redacted = |key: &str, value: &str| {
+        let err = SecretConfig::init_from_iter([(key, value)]).err().unwrap();
+        assert!(!format!("{err:?}").contains(value), "{err:?}");
+        assert!(!err.to_string().contains(value), "{err}");
+    };
+    assert_redacted("SECRET_FLAG", "hunter2");
+    assert_redacted("SECRET_PIN", "hunter2");
+    assert_redacted("SECRET_URL", "https://hunter2@${HOST");
+    assert_redacted("SECRET_TTL", "hunter2");
+
+    let err = SecretConfig::init_from_iter([("SECRET_TTL", "hunter2")])
+        .err()
+        .unwrap();
+    assert!(
+        matches!(err, Error::InvalidValue { value: ref redacted, .. } if redacted == "<redacted>"),
+        "{err:?}"
+    );
+}
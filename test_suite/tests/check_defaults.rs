@@ -0,0 +1,68 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, Error};
+
+#[derive(Envconfig)]
+pub struct DBConfig {
+    #[envconfig(from = "DB_HOST", default = "localhost")]
+    pub host: String,
+
+    #[envconfig(from = "DB_PORT", default = "80a")]
+    pub port: u16,
+}
+
+#[derive(Envconfig)]
+pub struct ValidConfig {
+    #[envconfig(from = "HTTP_PORT", default = "8080")]
+    pub http_port: u16,
+
+    #[envconfig(from = "HOSTS", json, default = "[\"localhost\"]")]
+    pub hosts: Vec<String>,
+
+    #[envconfig(from = "WORKERS", default = 4)]
+    pub workers: usize,
+
+    #[envconfig(from = "NAME")]
+    pub name: String,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(from = "HTTP_PORT", default = "8080")]
+    pub http_port: u16,
+
+    #[envconfig(nested)]
+    pub db: DBConfig,
+}
+
+#[derive(Envconfig)]
+pub struct InvalidJsonConfig {
+    #[envconfig(from = "HOSTS", json, default = "[localhost]")]
+    pub hosts: Vec<String>,
+}
+
+#[test]
+fn test_valid_defaults() {
+    ValidConfig::check_defaults().unwrap();
+}
+
+#[test]
+fn test_invalid_default_in_nested_config() {
+    let err = Config::check_defaults().err().unwrap();
+    let expected_err = Error::Nested {
+        path: "db.port".to_string(),
+        error: Box::new(Error::InvalidDefault {
+            name: "DB_PORT".to_string(),
+        }),
+    };
+    assert_eq!(err, expected_err);
+}
+
+#[test]
+fn test_invalid_json_default() {
+    let err = InvalidJsonConfig::check_defaults().err().unwrap();
+    let expected_err = Error::InvalidDefault {
+        name: "HOSTS".to_string(),
+    };
+    assert_eq!(err, expected_err);
+}
@@ -45,8 +45,11 @@ fn test_var_is_missing() {
     setup();
 
     let err = Config::init_from_env().err().unwrap();
-    let expected_err = Error::EnvVarMissing { name: "DB_HOST" };
-    assert_eq!(err, expected_err);
+    // The suggestions depend on the variables of the process environment
+    assert!(matches!(
+        err,
+        Error::EnvVarMissing { ref name, .. } if name == "DB_HOST"
+    ));
 }
 
 #[test]
@@ -56,6 +59,9 @@ fn test_key_is_missing() {
     let err = Config::init_from_hashmap(&HashMap::default())
         .err()
         .unwrap();
-    let expected_err = Error::EnvVarMissing { name: "DB_HOST" };
+    let expected_err = Error::EnvVarMissing {
+        name: "DB_HOST".to_string(),
+        suggestions: vec![],
+    };
     assert_eq!(err, expected_err);
 }
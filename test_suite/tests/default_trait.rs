@@ -0,0 +1,85 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, Error};
+use std::collections::HashMap;
+
+#[derive(Envconfig)]
+pub struct FieldConfig {
+    #[envconfig(from = "NAME", default)]
+    pub name: String,
+
+    #[envconfig(from = "RETRIES", default)]
+    pub retries: u32,
+
+    #[envconfig(from = "HOSTS", json, default)]
+    pub hosts: Vec<String>,
+}
+
+#[derive(Envconfig)]
+#[envconfig(default)]
+pub struct StructConfig {
+    #[envconfig(from = "HOST")]
+    pub host: String,
+
+    #[envconfig(from = "PORT")]
+    pub port: u16,
+
+    #[envconfig(from = "TIMEOUT", default = "30")]
+    pub timeout: u64,
+
+    #[envconfig(from = "USER")]
+    pub user: Option<String>,
+}
+
+impl Default for StructConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: 5432,
+            timeout: 10,
+            user: Some("admin".to_string()),
+        }
+    }
+}
+
+#[test]
+fn test_field_default_uses_default_trait() {
+    let config = FieldConfig::init_from_hashmap(&HashMap::new()).unwrap();
+    assert_eq!(config.name, "");
+    assert_eq!(config.retries, 0);
+    assert!(config.hosts.is_empty());
+}
+
+#[test]
+fn test_field_default_is_overridden_by_variable() {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("RETRIES".to_string(), "3".to_string());
+
+    let config = FieldConfig::init_from_hashmap(&hashmap).unwrap();
+    assert_eq!(config.retries, 3);
+}
+
+#[test]
+fn test_struct_default_fills_unset_fields() {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("PORT".to_string(), "6000".to_string());
+
+    let config = StructConfig::init_from_hashmap(&hashmap).unwrap();
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 6000);
+    // Own default of the field takes precedence over the struct default
+    assert_eq!(config.timeout, 30);
+    assert_eq!(config.user, Some("admin".to_string()));
+}
+
+#[test]
+fn test_struct_default_does_not_hide_parse_errors() {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("PORT".to_string(), "x".to_string());
+
+    let err = StructConfig::init_from_hashmap(&hashmap).err().unwrap();
+    let expected_err = Error::ParseError {
+        name: "PORT".to_string(),
+    };
+    assert_eq!(err, expected_err);
+}
@@ -0,0 +1,127 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, Error, FieldInfo};
+use std::collections::HashMap;
+
+#[derive(Envconfig)]
+pub struct S3Config {
+    #[envconfig(from = "S3_BUCKET")]
+    pub bucket: String,
+}
+
+#[derive(Envconfig)]
+pub struct LocalConfig {
+    #[envconfig(from = "LOCAL_PATH", default = "/var/data")]
+    pub path: String,
+}
+
+#[derive(Envconfig)]
+#[envconfig(from = "STORAGE")]
+pub enum Storage {
+    S3(S3Config),
+    Local(LocalConfig),
+    #[envconfig(rename = "mem")]
+    InMemory,
+}
+
+#[derive(Envconfig)]
+#[envconfig(default = "local")]
+pub enum StorageBackend {
+    S3(S3Config),
+    Local(LocalConfig),
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(nested)]
+    pub storage: Storage,
+}
+
+#[test]
+fn test_selects_variant_by_discriminator() {
+    let source = [("STORAGE", "s3"), ("S3_BUCKET", "assets")];
+    match Storage::init_from_iter(source).unwrap() {
+        Storage::S3(config) => assert_eq!(config.bucket, "assets"),
+        _ => panic!("Expected S3 storage"),
+    }
+
+    let source = [("STORAGE", "LOCAL")];
+    match Storage::init_from_iter(source).unwrap() {
+        Storage::Local(config) => assert_eq!(config.path, "/var/data"),
+        _ => panic!("Expected local storage"),
+    }
+
+    let source = [("STORAGE", "mem")];
+    assert!(matches!(
+        Storage::init_from_iter(source).unwrap(),
+        Storage::InMemory
+    ));
+}
+
+#[test]
+fn test_loads_only_variables_of_selected_variant() {
+    let source = [("STORAGE", "s3")];
+    let err = Config::init_from_iter(source).err().unwrap();
+    assert_eq!(
+        err,
+        Error::Nested {
+            path: "storage.s3.bucket".to_string(),
+            error: Box::new(Error::EnvVarMissing {
+                name: "S3_BUCKET".to_string(),
+                suggestions: vec![],
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_lists_valid_choices() {
+    let source = [("STORAGE", "gcs")];
+    let err = Storage::init_from_iter(source).err().unwrap();
+    assert_eq!(
+        err,
+        Error::InvalidVariant {
+            name: "STORAGE".to_string(),
+            value: "gcs".to_string(),
+            choices: vec!["s3", "local", "mem"],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Invalid value `gcs` of environment variable STORAGE, expected one of: s3, local, mem"
+    );
+
+    let err = Storage::init_from_hashmap(&HashMap::new()).err().unwrap();
+    assert_eq!(
+        err,
+        Error::EnvVarMissing {
+            name: "STORAGE".to_string(),
+            suggestions: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_default_variant() {
+    let backend = StorageBackend::init_from_hashmap(&HashMap::new()).unwrap();
+    assert!(matches!(backend, StorageBackend::Local(_)));
+    StorageBackend::check_defaults().unwrap();
+}
+
+#[test]
+fn test_describes_variants() {
+    let info = StorageBackend::struct_info();
+    let FieldInfo::Variants { var, variants } = &info.fields[0] else {
+        panic!("Expected variants");
+    };
+    assert_eq!(var.name, "STORAGE_BACKEND");
+    assert_eq!(var.default, Some("local"));
+    assert_eq!(
+        variants.iter().map(|v| v.value).collect::<Vec<_>>(),
+        vec!["s3", "local"]
+    );
+    assert_eq!(
+        info.var_names(),
+        vec!["STORAGE_BACKEND", "S3_BUCKET", "LOCAL_PATH"]
+    );
+}
@@ -0,0 +1,125 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, ValueFormat, VarInfo};
+
+#[derive(Envconfig)]
+pub struct DbConfig {
+    /// Host name of the database server
+    #[envconfig(from = "VARS_DB_HOST")]
+    pub host: String,
+
+    #[envconfig(from = "VARS_DB_PORT", default = "5432")]
+    pub port: u16,
+}
+
+#[derive(Envconfig)]
+pub struct FileConfig {
+    #[envconfig(from = "VARS_FILE_PATH")]
+    pub path: String,
+}
+
+#[derive(Envconfig)]
+#[envconfig(from = "VARS_STORAGE", default = "memory")]
+pub enum Storage {
+    File(FileConfig),
+    Memory,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(nested)]
+    pub db: DbConfig,
+
+    #[envconfig(nested)]
+    pub storage: Storage,
+
+    #[envconfig(from = "VARS_TIMEOUT", duration)]
+    pub timeout: Option<std::time::Duration>,
+
+    #[envconfig(skip)]
+    pub started: bool,
+}
+
+#[derive(Envconfig)]
+pub struct ReplicatedConfig {
+    #[envconfig(nested)]
+    pub primary: DbConfig,
+
+    #[envconfig(nested)]
+    pub replica: DbConfig,
+}
+
+#[test]
+fn test_lists_vars_of_nested_configs() {
+    let vars = Config::env_vars();
+    let names = vars.iter().map(|var| var.info.name).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "VARS_DB_HOST",
+            "VARS_DB_PORT",
+            "VARS_STORAGE",
+            "VARS_FILE_PATH",
+            "VARS_TIMEOUT"
+        ]
+    );
+    assert_eq!(vars[0].path, "db.host");
+    assert_eq!(
+        vars[0].info,
+        VarInfo {
+            name: "VARS_DB_HOST",
+            field: "host",
+            type_name: "String",
+            default: None,
+            required: true,
+            struct_default: false,
+            secret: false,
+            format: ValueFormat::FromStr,
+            doc: Some("Host name of the database server"),
+        }
+    );
+    assert_eq!(vars[1].info.default, Some("5432"));
+    assert!(!vars[1].info.required);
+    assert_eq!(vars[4].info.type_name, "Option<std::time::Duration>");
+    assert!(!vars[4].info.required);
+}
+
+#[test]
+fn test_lists_paths_of_fields() {
+    let paths = Config::env_vars()
+        .into_iter()
+        .map(|var| var.path)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            "db.host",
+            "db.port",
+            "storage",
+            "storage.file.path",
+            "timeout"
+        ]
+    );
+
+    let vars = ReplicatedConfig::env_vars();
+    let vars = vars
+        .iter()
+        .map(|var| (var.path.as_str(), var.info.name))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vars,
+        [
+            ("primary.host", "VARS_DB_HOST"),
+            ("primary.port", "VARS_DB_PORT"),
+            ("replica.host", "VARS_DB_HOST"),
+            ("replica.port", "VARS_DB_PORT")
+        ]
+    );
+}
+
+#[test]
+fn test_lists_vars_matching_struct_info() {
+    let info = Config::struct_info();
+    assert_eq!(Config::env_vars(), info.vars());
+    assert_eq!(info.var_names().len(), 5);
+}
@@ -0,0 +1,124 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, Error};
+use std::collections::HashMap;
+use std::env;
+use std::process::Command;
+
+#[derive(Envconfig)]
+pub struct DbConfig {
+    /// Host name of the database server
+    #[envconfig(from = "EXIT_DB_HOST")]
+    pub host: String,
+
+    #[envconfig(from = "EXIT_DB_PORT", default = "5432")]
+    pub port: u16,
+}
+
+#[derive(Envconfig)]
+pub struct FileConfig {
+    #[envconfig(from = "EXIT_FILE_PATH")]
+    pub path: String,
+}
+
+#[derive(Envconfig)]
+#[envconfig(from = "EXIT_STORAGE", default = "memory")]
+pub enum Storage {
+    File(FileConfig),
+    Memory,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(nested)]
+    pub db: DbConfig,
+
+    /// Number of worker threads
+    #[envconfig(from = "EXIT_WORKERS", default = "4")]
+    pub workers: u32,
+
+    #[envconfig(nested)]
+    pub storage: Storage,
+
+    #[envconfig(skip)]
+    pub started: bool,
+}
+
+#[test]
+fn test_collects_errors_of_all_fields() {
+    let mut source = HashMap::new();
+    source.insert("EXIT_DB_PORT".to_string(), "many".to_string());
+    source.insert("EXIT_WORKERS".to_string(), "-1".to_string());
+    source.insert("EXIT_STORAGE".to_string(), "file".to_string());
+    assert_eq!(
+        Config::collect_errors(&source),
+        vec![
+            Error::Nested {
+                path: "db.host".to_string(),
+                error: Box::new(Error::EnvVarMissing {
+                    name: "EXIT_DB_HOST".to_string(),
+                    suggestions: vec![],
+                }),
+            },
+            Error::Nested {
+                path: "db.port".to_string(),
+                error: Box::new(Error::ParseError {
+                    name: "EXIT_DB_PORT".to_string(),
+                }),
+            },
+            Error::ParseError {
+                name: "EXIT_WORKERS".to_string(),
+            },
+            Error::Nested {
+                path: "storage.file.path".to_string(),
+                error: Box::new(Error::EnvVarMissing {
+                    name: "EXIT_FILE_PATH".to_string(),
+                    suggestions: vec![],
+                }),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_collects_no_errors_of_valid_config() {
+    let mut source = HashMap::new();
+    source.insert("EXIT_DB_HOST".to_string(), "localhost".to_string());
+    assert_eq!(Config::collect_errors(&source), vec![]);
+
+    let mut source = HashMap::new();
+    source.insert("EXIT_STORAGE".to_string(), "tape".to_string());
+    assert!(matches!(
+        Storage::collect_errors(&source).as_slice(),
+        [Error::InvalidVariant { .. }]
+    ));
+}
+
+#[test]
+fn test_reports_errors_and_exits() {
+    // The test runs itself in a child process, which exits while loading the config
+    if env::var_os("EXIT_CHILD").is_some() {
+        let _config = Config::init_from_env_or_exit();
+        unreachable!("Expected the process to exit");
+    }
+
+    let output = Command::new(env::current_exe().unwrap())
+        .args(["test_reports_errors_and_exits", "--exact", "--nocapture"])
+        .env("EXIT_CHILD", "1")
+        .env("NO_COLOR", "1")
+        .env_remove("EXIT_DB_HOST")
+        .env("EXIT_DB_PORT", "many")
+        .env_remove("EXIT_WORKERS")
+        .env_remove("EXIT_STORAGE")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(78));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        "error: invalid configuration (2 problems)\n  \
+         - Environment variable EXIT_DB_HOST is missing (field `db.host`)\n      \
+         Host name of the database server\n  \
+         - Failed to parse environment variable EXIT_DB_PORT (field `db.port`)\n"
+    ));
+}
@@ -0,0 +1,121 @@
+extern crate envconfig;
+
+use envconfig::file::{from_dotenv_str, from_toml_file, from_toml_str, from_yaml_str};
+use envconfig::{overlay_env, Envconfig, Error};
+use std::env;
+use std::fs;
+
+#[derive(Envconfig)]
+pub struct DBConfig {
+    #[envconfig(from = "DB_HOST")]
+    pub host: String,
+    #[envconfig(from = "DB_PORT", default = "5432")]
+    pub port: u16,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(nested)]
+    pub db: DBConfig,
+
+    pub http_port: u16,
+
+    #[envconfig(json, default = "[]")]
+    pub allowed_hosts: Vec<String>,
+}
+
+const TOML: &str = r#"
+http_port = 8080
+allowed_hosts = ["example.com", "example.org"]
+
+[db]
+host = "localhost"
+port = 6000
+"#;
+
+const YAML: &str = "
+http-port: 8080
+allowed_hosts:
+  - example.com
+  - example.org
+db:
+  host: localhost
+  port: 6000
+";
+
+fn assert_config(config: &Config) {
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 6000);
+    assert_eq!(config.http_port, 8080);
+    assert_eq!(config.allowed_hosts, vec!["example.com", "example.org"]);
+}
+
+#[test]
+fn test_loads_from_toml() {
+    let source = from_toml_str(TOML).unwrap();
+    assert_eq!(source.get("DB_HOST").unwrap(), "localhost");
+    assert_config(&Config::init_from_hashmap(&source).unwrap());
+}
+
+#[test]
+fn test_loads_from_yaml() {
+    let source = from_yaml_str(YAML).unwrap();
+    assert_eq!(source.get("HTTP_PORT").unwrap(), "8080");
+    assert_config(&Config::init_from_hashmap(&source).unwrap());
+}
+
+#[test]
+fn test_loads_from_dotenv() {
+    let source = from_dotenv_str(
+        "# database\nexport DB_HOST=\"localhost\"\n\nDB_PORT = 6000\nHTTP_PORT='8080'\n\
+         ALLOWED_HOSTS=[\"example.com\",\"example.org\"]\n",
+    )
+    .unwrap();
+    assert_eq!(source.get("DB_HOST").unwrap(), "localhost");
+    assert_config(&Config::init_from_hashmap(&source).unwrap());
+}
+
+#[test]
+fn test_loads_from_toml_file() {
+    let path = env::temp_dir().join(format!("envconfig-file-{}.toml", std::process::id()));
+    fs::write(&path, TOML).unwrap();
+
+    let source = from_toml_file(&path).unwrap();
+    assert_config(&Config::init_from_hashmap(&source).unwrap());
+}
+
+#[test]
+fn test_env_overlays_file() {
+    env::set_var("FILE_TEST_DB_PORT", "7000");
+
+    let source = from_toml_str("[file_test.db]\nport = 6000").unwrap();
+    assert_eq!(source.get("FILE_TEST_DB_PORT").unwrap(), "6000");
+
+    let source = overlay_env(source);
+    assert_eq!(source.get("FILE_TEST_DB_PORT").unwrap(), "7000");
+}
+
+#[test]
+fn test_fails_on_invalid_documents() {
+    let err = from_toml_str("[db").err().unwrap();
+    assert!(matches!(err, Error::SourceError { .. }));
+
+    let err = from_yaml_str("- a\n- b").err().unwrap();
+    let expected_err = Error::SourceError {
+        message: "Invalid YAML: expected a mapping at the top level".to_string(),
+    };
+    assert_eq!(err, expected_err);
+
+    let err = from_dotenv_str("DB_HOST=localhost\n=6000").err().unwrap();
+    let expected_err = Error::SourceError {
+        message: "Invalid .env file: line 2 is not a KEY=VALUE pair".to_string(),
+    };
+    assert_eq!(err, expected_err);
+
+    let err = from_toml_file("/nonexistent/config.toml").err().unwrap();
+    assert!(matches!(
+        err,
+        Error::IoError { ref path, kind: std::io::ErrorKind::NotFound, .. }
+            if path == "/nonexistent/config.toml"
+    ));
+}
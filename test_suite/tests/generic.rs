@@ -0,0 +1,76 @@
+extern crate envconfig;
+
+use envconfig::Envconfig;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+#[derive(Envconfig)]
+pub struct PostgresConfig {
+    #[envconfig(from = "PG_URL")]
+    pub url: String,
+}
+
+#[derive(Envconfig)]
+pub struct PoolConfig<Backend: Envconfig> {
+    #[envconfig(from = "POOL_SIZE", default = "10")]
+    pub size: u32,
+
+    #[envconfig(nested)]
+    pub backend: Backend,
+}
+
+#[derive(Envconfig)]
+pub struct Limit<T>
+where
+    T: FromStr + Debug,
+{
+    #[envconfig(from = "LIMIT")]
+    pub value: T,
+
+    #[envconfig(from = "LIMIT_MAX")]
+    pub max: Option<T>,
+}
+
+#[derive(Envconfig)]
+pub struct AppConfig {
+    #[envconfig(nested)]
+    pub pool: PoolConfig<PostgresConfig>,
+
+    #[envconfig(nested)]
+    pub limit: Limit<u8>,
+}
+
+#[test]
+fn test_generic_nested_config() {
+    let config =
+        PoolConfig::<PostgresConfig>::init_from_iter([("PG_URL", "postgres://localhost")]).unwrap();
+    assert_eq!(config.size, 10);
+    assert_eq!(config.backend.url, "postgres://localhost");
+}
+
+#[test]
+fn test_generic_value_with_where_clause() {
+    let config = Limit::<u8>::init_from_iter([("LIMIT", "7")]).unwrap();
+    assert_eq!(config.value, 7);
+    assert_eq!(config.max, None);
+
+    let config = Limit::<f64>::init_from_iter([("LIMIT", "7")]).unwrap();
+    assert!((config.value - 7.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_generic_configs_can_be_nested() {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("PG_URL".to_string(), "postgres://localhost".to_string());
+    hashmap.insert("LIMIT".to_string(), "7".to_string());
+
+    let config = AppConfig::init_from_hashmap(&hashmap).unwrap();
+    assert_eq!(config.pool.backend.url, "postgres://localhost");
+    assert_eq!(config.limit.value, 7);
+
+    hashmap.insert("LIMIT".to_string(), "300".to_string());
+    let err = AppConfig::init_from_hashmap(&hashmap).err().unwrap();
+    assert_eq!(err.path(), Some("limit.value"));
+    assert_eq!(err.var_name(), Some("LIMIT"));
+}
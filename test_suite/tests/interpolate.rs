@@ -0,0 +1,153 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, Error};
+use std::env;
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(from = "BASE_URL")]
+    pub base_url: String,
+
+    #[envconfig(from = "CALLBACK_URL", interpolate)]
+    pub callback_url: String,
+
+    #[envconfig(from = "HEALTH_URL", interpolate, default = "${BASE_URL}/health")]
+    pub health_url: String,
+
+    #[envconfig(from = "LOG_DIR", interpolate)]
+    pub log_dir: Option<String>,
+
+    #[envconfig(from = "RAW_TEMPLATE")]
+    pub raw_template: Option<String>,
+}
+
+#[derive(Envconfig)]
+#[envconfig(interpolate)]
+pub struct ServerConfig {
+    #[envconfig(from = "SERVER_PORT")]
+    pub port: u16,
+
+    #[envconfig(from = "SERVER_ADDR", default = "0.0.0.0:${SERVER_PORT}")]
+    pub addr: String,
+}
+
+#[test]
+fn test_expands_references() {
+    let source = [
+        ("BASE_URL", "https://example.com"),
+        ("CALLBACK_URL", "${BASE_URL}/callback"),
+        ("LOG_DIR", "${DATA_DIR:-/var/lib}/logs"),
+        ("RAW_TEMPLATE", "${BASE_URL}"),
+    ];
+    let config = Config::init_from_iter(source).unwrap();
+    assert_eq!(config.callback_url, "https://example.com/callback");
+    assert_eq!(config.health_url, "https://example.com/health");
+    assert_eq!(config.log_dir, Some("/var/lib/logs".to_string()));
+    assert_eq!(config.raw_template, Some("${BASE_URL}".to_string()));
+}
+
+#[test]
+fn test_expands_references_recursively() {
+    let source = [
+        ("HOST", "example.com"),
+        ("BASE_URL", "https://${HOST}"),
+        (
+            "CALLBACK_URL",
+            "${BASE_URL}/callback?cost=$$5&${MISSING:-${HOST}}",
+        ),
+    ];
+    let config = Config::init_from_iter(source).unwrap();
+    assert_eq!(
+        config.callback_url,
+        "https://example.com/callback?cost=$5&example.com"
+    );
+}
+
+#[test]
+fn test_expands_empty_references() {
+    let source = [
+        ("EMPTY", ""),
+        ("BASE_URL", "https://example.com"),
+        ("CALLBACK_URL", "${BASE_URL}${EMPTY}/callback"),
+        ("LOG_DIR", "${EMPTY:-/var/lib}/logs"),
+    ];
+    let config = Config::init_from_iter(source).unwrap();
+    assert_eq!(config.callback_url, "https://example.com/callback");
+    assert_eq!(config.log_dir, Some("/var/lib/logs".to_string()));
+}
+
+#[test]
+fn test_expands_references_in_env() {
+    env::set_var("INTERPOLATE_BASE_URL", "https://example.org");
+    env::set_var("BASE_URL", "${INTERPOLATE_BASE_URL}");
+    env::set_var("CALLBACK_URL", "${INTERPOLATE_BASE_URL}/callback");
+
+    let config = Config::init_from_env().unwrap();
+    assert_eq!(config.base_url, "${INTERPOLATE_BASE_URL}");
+    assert_eq!(config.callback_url, "https://example.org/callback");
+    assert_eq!(config.health_url, "https://example.org/health");
+}
+
+#[test]
+fn test_struct_level_interpolation() {
+    let config = ServerConfig::init_from_iter([("SERVER_PORT", "8080")]).unwrap();
+    assert_eq!(config.addr, "0.0.0.0:8080");
+    assert_eq!(ServerConfig::check_defaults(), Ok(()));
+}
+
+#[test]
+fn test_unresolved_reference_error() {
+    let source = [
+        ("BASE_URL", "https://example.com"),
+        ("CALLBACK_URL", "${PUBLIC_URL}/callback"),
+    ];
+    let err = Config::init_from_iter(source).err().unwrap();
+    assert_eq!(
+        err,
+        Error::UnresolvedReference {
+            name: "CALLBACK_URL".to_string(),
+            reference: "PUBLIC_URL".to_string(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Environment variable CALLBACK_URL references ${PUBLIC_URL}, which is not set"
+    );
+
+    let source = [
+        ("BASE_URL", "https://example.com"),
+        ("CALLBACK_URL", "${BASE_URL/callback"),
+    ];
+    let err = Config::init_from_iter(source).err().unwrap();
+    assert!(matches!(
+        err,
+        Error::InvalidValue { ref name, .. } if name == "CALLBACK_URL"
+    ));
+}
+
+#[test]
+fn test_reference_cycle_error() {
+    let source = [
+        ("BASE_URL", "https://example.com"),
+        ("CALLBACK_URL", "${A}"),
+        ("A", "${B}"),
+        ("B", "${A}"),
+    ];
+    let err = Config::init_from_iter(source).err().unwrap();
+    assert_eq!(
+        err,
+        Error::ReferenceCycle {
+            name: "CALLBACK_URL".to_string(),
+            cycle: vec![
+                "CALLBACK_URL".to_string(),
+                "A".to_string(),
+                "B".to_string(),
+                "A".to_string(),
+            ],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Environment variable CALLBACK_URL contains a reference cycle: CALLBACK_URL -> A -> B -> A"
+    );
+}
@@ -0,0 +1,70 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, Error};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasherDefault;
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(from = "DB_HOST")]
+    pub db_host: String,
+
+    #[envconfig(from = "DB_PORT", default = "5432")]
+    pub db_port: u16,
+}
+
+#[test]
+fn test_inits_config_from_array_of_tuples() {
+    let config = Config::init_from_iter([("DB_HOST", "localhost"), ("DB_PORT", "6543")]).unwrap();
+    assert_eq!(config.db_host, "localhost");
+    assert_eq!(config.db_port, 6543);
+}
+
+#[test]
+fn test_inits_config_from_btree_map() {
+    let mut vars = BTreeMap::new();
+    vars.insert("DB_HOST".to_string(), "localhost".to_string());
+
+    let config = Config::init_from_iter(&vars).unwrap();
+    assert_eq!(config.db_host, "localhost");
+    assert_eq!(config.db_port, 5432);
+
+    let config = Config::init_from_iter(vars).unwrap();
+    assert_eq!(config.db_host, "localhost");
+}
+
+#[test]
+fn test_inits_config_from_hashmap_of_str() {
+    let mut vars: HashMap<&str, &str> = HashMap::new();
+    vars.insert("DB_HOST", "localhost");
+
+    let config = Config::init_from_iter(&vars).unwrap();
+    assert_eq!(config.db_host, "localhost");
+}
+
+#[test]
+fn test_inits_config_from_hashmap_with_custom_hasher() {
+    let mut vars: HashMap<String, String, BuildHasherDefault<std::hash::DefaultHasher>> =
+        HashMap::default();
+    vars.insert("DB_HOST".to_string(), "localhost".to_string());
+
+    let config = Config::init_from_iter(&vars).unwrap();
+    assert_eq!(config.db_host, "localhost");
+}
+
+#[test]
+fn test_last_value_wins_and_errors_are_reported() {
+    let config = Config::init_from_iter(vec![("DB_HOST", "first"), ("DB_HOST", "second")]).unwrap();
+    assert_eq!(config.db_host, "second");
+
+    let err = Config::init_from_iter(Vec::<(String, String)>::new())
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        Error::EnvVarMissing {
+            name: "DB_HOST".to_string(),
+            suggestions: vec![],
+        }
+    );
+}
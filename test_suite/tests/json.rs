@@ -0,0 +1,84 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, Error};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Route {
+    pub path: String,
+    pub upstream: String,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(from = "FEATURE_FLAGS", json)]
+    pub feature_flags: HashMap<String, bool>,
+
+    #[envconfig(from = "ROUTES", json, default = "[]")]
+    pub routes: Vec<Route>,
+
+    #[envconfig(from = "LIMITS", json)]
+    pub limits: Option<Vec<u32>>,
+}
+
+fn setup() {
+    env::remove_var("FEATURE_FLAGS");
+    env::remove_var("ROUTES");
+    env::remove_var("LIMITS");
+}
+
+#[test]
+fn test_deserializes_json_from_env() {
+    setup();
+
+    env::set_var("FEATURE_FLAGS", r#"{"dark_mode": true, "beta": false}"#);
+    env::set_var("ROUTES", r#"[{"path": "/api", "upstream": "api:8080"}]"#);
+    env::set_var("LIMITS", "[10, 20]");
+
+    let config = Config::init_from_env().unwrap();
+    assert_eq!(config.feature_flags.get("dark_mode"), Some(&true));
+    assert_eq!(config.feature_flags.get("beta"), Some(&false));
+    assert_eq!(
+        config.routes,
+        vec![Route {
+            path: "/api".to_string(),
+            upstream: "api:8080".to_string()
+        }]
+    );
+    assert_eq!(config.limits, Some(vec![10, 20]));
+}
+
+#[test]
+fn test_uses_default_and_none_from_hashmap() {
+    setup();
+
+    let mut hashmap = HashMap::new();
+    hashmap.insert("FEATURE_FLAGS".to_string(), "{}".to_string());
+
+    let config = Config::init_from_hashmap(&hashmap).unwrap();
+    assert!(config.feature_flags.is_empty());
+    assert!(config.routes.is_empty());
+    assert_eq!(config.limits, None);
+}
+
+#[test]
+fn test_fails_on_invalid_json() {
+    setup();
+
+    let mut hashmap = HashMap::new();
+    hashmap.insert(
+        "FEATURE_FLAGS".to_string(),
+        r#"{"beta": "yes"}"#.to_string(),
+    );
+
+    let err = Config::init_from_hashmap(&hashmap).err().unwrap();
+    match err {
+        Error::DeserializeError { name, message } => {
+            assert_eq!(name, "FEATURE_FLAGS");
+            assert!(message.contains("expected a boolean"), "{message}");
+        }
+        err => panic!("Unexpected error: {err:?}"),
+    }
+}
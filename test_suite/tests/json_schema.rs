@@ -0,0 +1,194 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, FieldInfo, ValueFormat, VarInfo};
+use serde_json::json;
+
+/// Database connection settings.
+#[derive(Envconfig)]
+pub struct DBConfig {
+    /// Database host.
+    #[envconfig(from = "DB_HOST")]
+    pub host: String,
+
+    #[envconfig(from = "DB_PORT", default = "5432")]
+    pub port: u16,
+
+    #[envconfig(from = "DB_PASSWORD", secret)]
+    pub password: Option<String>,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    /// Primary database.
+    #[envconfig(nested)]
+    pub db: DBConfig,
+
+    #[envconfig(json, default = "[]")]
+    pub routes: Vec<String>,
+}
+
+#[test]
+fn test_struct_info() {
+    let info = DBConfig::struct_info();
+    assert_eq!(info.name, "DBConfig");
+    assert_eq!(info.doc, Some("Database connection settings."));
+    assert_eq!(
+        info.fields[2],
+        FieldInfo::Var(VarInfo {
+            name: "DB_PASSWORD",
+            field: "password",
+            type_name: "Option<String>",
+            default: None,
+            required: false,
+            struct_default: false,
+            secret: true,
+            format: ValueFormat::FromStr,
+            doc: None,
+        })
+    );
+}
+
+/// Implemented by hand, without `struct_info()` and `check_defaults()`
+pub struct ManualConfig;
+
+impl Envconfig for ManualConfig {
+    fn init() -> Result<Self, envconfig::Error> {
+        Self::init_from_env()
+    }
+
+    fn init_from_env() -> Result<Self, envconfig::Error> {
+        Ok(ManualConfig)
+    }
+
+    fn init_from_hashmap(
+        _hashmap: &std::collections::HashMap<String, String>,
+    ) -> Result<Self, envconfig::Error> {
+        Ok(ManualConfig)
+    }
+}
+
+#[test]
+fn test_default_struct_info() {
+    let info = ManualConfig::struct_info();
+    assert!(info.name.ends_with("ManualConfig"));
+    assert_eq!(info.fields, vec![]);
+    assert_eq!(ManualConfig::check_defaults(), Ok(()));
+}
+
+#[test]
+fn test_json_schema() {
+    let schema = Config::json_schema();
+    let expected = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Config",
+        "type": "object",
+        "properties": {
+            "db": {
+                "title": "DBConfig",
+                "description": "Primary database.",
+                "type": "object",
+                "properties": {
+                    "host": {
+                        "type": "string",
+                        "description": "Database host.",
+                        "x-env-var": "DB_HOST",
+                        "x-rust-type": "String"
+                    },
+                    "port": {
+                        "type": "integer",
+                        "default": 5432,
+                        "x-env-var": "DB_PORT",
+                        "x-rust-type": "u16"
+                    },
+                    "password": {
+                        "type": "string",
+                        "writeOnly": true,
+                        "x-env-var": "DB_PASSWORD",
+                        "x-rust-type": "Option<String>"
+                    }
+                },
+                "required": ["host"]
+            },
+            "routes": {
+                "type": "string",
+                "contentMediaType": "application/json",
+                "default": "[]",
+                "x-env-var": "ROUTES",
+                "x-rust-type": "Vec<String>"
+            }
+        },
+        "required": ["db"]
+    });
+    assert_eq!(schema, expected);
+}
+
+#[derive(Envconfig)]
+pub struct PoolConfig {
+    #[envconfig(from = "POOL_SIZE", default = "10")]
+    pub size: u32,
+}
+
+#[derive(Envconfig)]
+pub struct S3Config {
+    #[envconfig(from = "S3_BUCKET")]
+    pub bucket: String,
+}
+
+#[derive(Envconfig)]
+#[envconfig(from = "STORAGE", default = "memory")]
+pub enum Storage {
+    S3(S3Config),
+    Memory,
+}
+
+#[derive(Envconfig)]
+pub struct StorageConfig {
+    #[envconfig(nested)]
+    pub pool: PoolConfig,
+
+    #[envconfig(nested)]
+    pub storage: Storage,
+}
+
+#[test]
+fn test_json_schema_of_optional_nested_configs_and_variants() {
+    let schema = StorageConfig::json_schema();
+    assert_eq!(schema["required"], json!([]));
+    assert_eq!(
+        schema["properties"]["storage"],
+        json!({
+            "title": "Storage",
+            "type": "object",
+            "properties": {
+                "storage": {
+                    "type": "string",
+                    "default": "memory",
+                    "enum": ["s3", "memory"],
+                    "x-env-var": "STORAGE",
+                    "x-rust-type": "String"
+                }
+            },
+            "required": [],
+            "allOf": [
+                {
+                    "if": {
+                        "properties": { "storage": { "const": "s3" } },
+                        "required": ["storage"]
+                    },
+                    "then": {
+                        "title": "S3Config",
+                        "type": "object",
+                        "properties": {
+                            "bucket": {
+                                "type": "string",
+                                "x-env-var": "S3_BUCKET",
+                                "x-rust-type": "String"
+                            }
+                        },
+                        "required": ["bucket"]
+                    }
+                }
+            ]
+        })
+    );
+}
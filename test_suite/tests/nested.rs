@@ -3,6 +3,7 @@ extern crate envconfig;
 use envconfig::{Envconfig, Error};
 use std::collections::HashMap;
 use std::env;
+use std::error::Error as StdError;
 
 #[derive(Envconfig)]
 pub struct DBConfig {
@@ -27,6 +28,24 @@ pub struct ConfigDouble {
     pub db2: DBConfig,
 }
 
+#[derive(Envconfig)]
+pub struct DatabaseConfig {
+    #[envconfig(nested)]
+    pub replica: DBConfig,
+}
+
+#[derive(Envconfig)]
+pub struct AppConfig {
+    #[envconfig(nested)]
+    pub database: DatabaseConfig,
+}
+
+#[derive(Envconfig)]
+pub struct RootConfig {
+    #[envconfig(nested)]
+    pub app: AppConfig,
+}
+
 fn setup() {
     env::remove_var("DB_HOST");
     env::remove_var("DB_PORT");
@@ -64,8 +83,15 @@ fn test_nesting_env_error() {
     env::set_var("DB_HOST", "localhost");
 
     let err = Config::init_from_env().err().unwrap();
-    let expected_err = Error::EnvVarMissing { name: "DB_PORT" };
-    assert_eq!(err, expected_err);
+    assert_eq!(err.path(), Some("db.port"));
+    // The suggestions depend on the variables of the process environment
+    let Error::Nested { error, .. } = err else {
+        panic!("Expected an error of the nested config");
+    };
+    assert!(matches!(
+        *error,
+        Error::EnvVarMissing { ref name, .. } if name == "DB_PORT"
+    ));
 }
 
 #[test]
@@ -76,8 +102,18 @@ fn test_nesting_hashmap_error() {
     hashmap.insert("DB_HOST".to_string(), "localhost".to_string());
 
     let err = Config::init_from_hashmap(&hashmap).err().unwrap();
-    let expected_err = Error::EnvVarMissing { name: "DB_PORT" };
+    let expected_err = Error::Nested {
+        path: "db.port".to_string(),
+        error: Box::new(Error::EnvVarMissing {
+            name: "DB_PORT".to_string(),
+            suggestions: vec![],
+        }),
+    };
     assert_eq!(err, expected_err);
+    assert_eq!(
+        err.to_string(),
+        "Environment variable DB_PORT is missing (field `db.port`)"
+    );
 }
 
 #[test]
@@ -108,3 +144,40 @@ fn test_duplicated_are_allowed_in_hashmap() {
     assert_eq!(config.db2.host, "localhost");
     assert_eq!(config.db2.port, 5432u16);
 }
+
+#[test]
+fn test_deeply_nested_error_path() {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("DB_HOST".to_string(), "localhost".to_string());
+    hashmap.insert("DB_PORT".to_string(), "not-a-port".to_string());
+
+    let err = RootConfig::init_from_hashmap(&hashmap).err().unwrap();
+    assert_eq!(
+        err,
+        Error::Nested {
+            path: "app.database.replica.port".to_string(),
+            error: Box::new(Error::ParseError {
+                name: "DB_PORT".to_string()
+            }),
+        }
+    );
+    assert_eq!(err.var_name(), Some("DB_PORT"));
+    assert_eq!(
+        err.to_string(),
+        "Failed to parse environment variable DB_PORT (field `app.database.replica.port`)"
+    );
+}
+
+#[test]
+fn test_nested_error_source() {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("DB_PORT".to_string(), "5432".to_string());
+
+    let err = Config::init_from_hashmap(&hashmap).err().unwrap();
+    let source = err.source().unwrap();
+    assert_eq!(
+        source.to_string(),
+        "Environment variable DB_HOST is missing"
+    );
+    assert!(source.source().is_none());
+}
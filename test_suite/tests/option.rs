@@ -56,7 +56,9 @@ fn test_env_var_is_invalid() {
 
     env::set_var("PORT", "xyz");
     let err = Config::init_from_env().err().unwrap();
-    let expected_err = Error::ParseError { name: "PORT" };
+    let expected_err = Error::ParseError {
+        name: "PORT".to_string(),
+    };
     assert_eq!(err, expected_err);
 }
 
@@ -68,6 +70,8 @@ fn test_hashmap_key_is_invalid() {
     hashmap.insert("PORT".to_string(), "xyz".to_string());
 
     let err = Config::init_from_hashmap(&hashmap).err().unwrap();
-    let expected_err = Error::ParseError { name: "PORT" };
+    let expected_err = Error::ParseError {
+        name: "PORT".to_string(),
+    };
     assert_eq!(err, expected_err);
 }
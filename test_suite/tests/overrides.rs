@@ -0,0 +1,82 @@
+extern crate envconfig;
+
+use envconfig::{env_with_overrides, Envconfig, Error, UnknownVar};
+use std::env;
+
+#[derive(Envconfig)]
+pub struct DBConfig {
+    #[envconfig(from = "DB_HOST")]
+    pub host: String,
+    #[envconfig(from = "DB_PORT", default = "5432")]
+    pub port: u16,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(nested)]
+    pub db: DBConfig,
+
+    #[envconfig(from = "HTTP_PORT", default = "8080")]
+    pub http_port: u16,
+}
+
+fn setup() {
+    env::remove_var("DB_HOST");
+    env::remove_var("DB_PORT");
+    env::remove_var("HTTP_PORT");
+}
+
+#[test]
+fn test_overrides_take_precedence_over_env() {
+    setup();
+
+    env::set_var("DB_HOST", "localhost");
+    env::set_var("DB_PORT", "6000");
+
+    let source = env_with_overrides::<Config, _>(["DB_PORT=7000", "HTTP_PORT=9090"]).unwrap();
+    let config = Config::init_from_hashmap(&source).unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 7000);
+    assert_eq!(config.http_port, 9090);
+}
+
+#[test]
+fn test_values_may_contain_equal_signs() {
+    let args = vec!["DB_HOST=host=db".to_string()];
+    let source = env_with_overrides::<Config, _>(&args).unwrap();
+    let config = Config::init_from_hashmap(&source).unwrap();
+    assert_eq!(config.db.host, "host=db");
+}
+
+#[test]
+fn test_rejects_unknown_keys() {
+    let err = env_with_overrides::<Config, _>(["DB_HSOT=localhost", "DB_PORT=1", "PORT=2"])
+        .err()
+        .unwrap();
+    let expected_err = Error::UnknownVars {
+        vars: vec![
+            UnknownVar {
+                name: "DB_HSOT".to_string(),
+                suggestions: vec!["DB_HOST"],
+            },
+            UnknownVar {
+                name: "PORT".to_string(),
+                suggestions: vec!["DB_PORT", "HTTP_PORT"],
+            },
+        ],
+    };
+    assert_eq!(
+        err.to_string(),
+        "Unknown variables: DB_HSOT (did you mean DB_HOST?), PORT (did you mean DB_PORT or HTTP_PORT?)"
+    );
+    assert_eq!(err, expected_err);
+}
+
+#[test]
+fn test_rejects_invalid_arguments() {
+    let err = env_with_overrides::<Config, _>(["DB_HOST"]).err().unwrap();
+    let expected_err = Error::InvalidOverride {
+        arg: "DB_HOST".to_string(),
+    };
+    assert_eq!(err, expected_err);
+}
@@ -0,0 +1,112 @@
+extern crate envconfig;
+
+use envconfig::reload::Reloader;
+use envconfig::{Envconfig, Error};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(from = "DB_HOST")]
+    pub db_host: String,
+
+    #[envconfig(from = "DB_PORT", default = "5432")]
+    pub db_port: u16,
+}
+
+fn config_path(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("envconfig-reload-{}-{name}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("config.env")
+}
+
+#[test]
+fn test_loads_config_from_file() {
+    let path = config_path("load");
+    fs::write(
+        &path,
+        "# database\nexport DB_HOST=\"localhost\"\n\nDB_PORT = 5433\n",
+    )
+    .unwrap();
+
+    let reloader = Reloader::<Config>::new(&path).unwrap();
+    let config = reloader.handle().load();
+    assert_eq!(config.db_host, "localhost");
+    assert_eq!(config.db_port, 5433);
+}
+
+#[test]
+fn test_reload_replaces_config() {
+    let path = config_path("replace");
+    fs::write(&path, "DB_HOST=localhost\n").unwrap();
+
+    let reloader = Reloader::<Config>::new(&path).unwrap();
+    let handle = reloader.handle();
+    let old_config = handle.load();
+
+    fs::write(&path, "DB_HOST=db.example.com\nDB_PORT=6000\n").unwrap();
+    reloader.reload().unwrap();
+
+    assert_eq!(old_config.db_host, "localhost");
+    assert_eq!(handle.load().db_host, "db.example.com");
+    assert_eq!(handle.load().db_port, 6000);
+}
+
+#[test]
+fn test_failed_reload_keeps_old_config() {
+    let path = config_path("keep");
+    fs::write(&path, "DB_HOST=localhost\n").unwrap();
+
+    let reloader = Reloader::<Config>::new(&path).unwrap();
+
+    fs::write(&path, "DB_HOST=localhost\nDB_PORT=67000\n").unwrap();
+    let err = reloader.reload().err().unwrap();
+    assert!(matches!(
+        err,
+        Error::ParseError { ref name } if name == "DB_PORT"
+    ));
+
+    fs::write(&path, "DB_HOST=localhost\nDB_PORT\n").unwrap();
+    let err = reloader.reload().err().unwrap();
+    assert_eq!(
+        err,
+        Error::SourceError {
+            message: "Invalid .env file: line 2 is not a KEY=VALUE pair".to_string(),
+        }
+    );
+
+    fs::remove_file(&path).unwrap();
+    let err = reloader.reload().err().unwrap();
+    assert!(matches!(
+        err,
+        Error::IoError {
+            kind: std::io::ErrorKind::NotFound,
+            ..
+        }
+    ));
+
+    let config = reloader.handle().load();
+    assert_eq!(config.db_host, "localhost");
+    assert_eq!(config.db_port, 5432);
+}
+
+#[test]
+fn test_watch_file_reloads_on_change() {
+    let path = config_path("watch");
+    fs::write(&path, "DB_HOST=localhost\n").unwrap();
+
+    let reloader = Reloader::<Config>::new(&path).unwrap();
+    let handle = reloader.handle();
+    let _watcher = reloader.watch_file().unwrap();
+
+    fs::write(&path, "DB_HOST=db.example.com\n").unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while handle.load().db_host != "db.example.com" && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(20));
+    }
+    assert_eq!(handle.load().db_host, "db.example.com");
+}
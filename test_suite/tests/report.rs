@@ -0,0 +1,187 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, Origin, Provenance};
+use std::collections::HashMap;
+use std::env;
+
+#[derive(Envconfig)]
+pub struct DbConfig {
+    #[envconfig(from = "REPORT_DB_HOST")]
+    pub host: String,
+
+    #[envconfig(from = "REPORT_DB_PORT", default = "5432")]
+    pub port: u16,
+
+    #[envconfig(from = "REPORT_DB_PASSWORD", secret)]
+    pub password: String,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(nested)]
+    pub db: DbConfig,
+
+    #[envconfig(from = "REPORT_LOG_LEVEL")]
+    pub log_level: Option<String>,
+
+    #[envconfig(from = "REPORT_WORKERS", default = 4)]
+    pub workers: u32,
+}
+
+#[derive(Envconfig)]
+pub struct FileConfig {
+    #[envconfig(from = "REPORT_FILE_PATH")]
+    pub path: String,
+}
+
+#[derive(Envconfig)]
+#[envconfig(from = "REPORT_STORAGE")]
+pub enum Storage {
+    File(FileConfig),
+    Memory,
+}
+
+#[derive(Envconfig)]
+pub struct StorageConfig {
+    #[envconfig(nested)]
+    pub storage: Storage,
+}
+
+#[derive(Envconfig)]
+#[envconfig(default)]
+pub struct DefaultConfig {
+    #[envconfig(from = "REPORT_DEFAULT_HOST")]
+    pub host: Option<String>,
+
+    #[envconfig(from = "REPORT_DEFAULT_PORT")]
+    pub port: u16,
+}
+
+impl Default for DefaultConfig {
+    fn default() -> Self {
+        DefaultConfig {
+            host: Some("localhost".to_string()),
+            port: 8080,
+        }
+    }
+}
+
+#[test]
+fn test_reports_origin_of_each_field() {
+    let mut source = HashMap::new();
+    source.insert("REPORT_DB_HOST".to_string(), "db.local".to_string());
+    source.insert("REPORT_DB_PASSWORD".to_string(), "hunter2".to_string());
+    let (config, report) = Config::init_from_hashmap_with_report(&source).unwrap();
+    assert_eq!(config.db.host, "db.local");
+
+    let entry =
+        |path: &str, var: Option<&'static str>, origin: Origin, value: Option<&str>| Provenance {
+            path: path.to_string(),
+            var,
+            origin,
+            value: value.map(str::to_string),
+        };
+    assert_eq!(
+        report.entries(),
+        &[
+            entry("db", None, Origin::Nested, None),
+            entry(
+                "db.host",
+                Some("REPORT_DB_HOST"),
+                Origin::HashMap,
+                Some("db.local")
+            ),
+            entry(
+                "db.port",
+                Some("REPORT_DB_PORT"),
+                Origin::Default,
+                Some("5432")
+            ),
+            entry(
+                "db.password",
+                Some("REPORT_DB_PASSWORD"),
+                Origin::HashMap,
+                Some("<redacted>")
+            ),
+            entry("log_level", Some("REPORT_LOG_LEVEL"), Origin::Unset, None),
+            entry(
+                "workers",
+                Some("REPORT_WORKERS"),
+                Origin::Default,
+                Some("4")
+            ),
+        ]
+    );
+    assert_eq!(
+        report.to_string(),
+        "db (nested)\n\
+         db.host (hashmap) REPORT_DB_HOST = db.local\n\
+         db.port (default) REPORT_DB_PORT = 5432\n\
+         db.password (hashmap) REPORT_DB_PASSWORD = <redacted>\n\
+         log_level (unset) REPORT_LOG_LEVEL\n\
+         workers (default) REPORT_WORKERS = 4\n"
+    );
+}
+
+#[test]
+fn test_reports_env_origin() {
+    env::set_var("REPORT_DB_HOST", "env.local");
+    env::set_var("REPORT_DB_PASSWORD", "secret");
+    env::set_var("REPORT_LOG_LEVEL", "debug");
+
+    let (config, report) = Config::init_from_env_with_report().unwrap();
+    assert_eq!(config.log_level, Some("debug".to_string()));
+
+    let host = report.get("db.host").unwrap();
+    assert_eq!(host.origin, Origin::Environment);
+    assert_eq!(host.value, Some("env.local".to_string()));
+    assert_eq!(report.get("log_level").unwrap().origin, Origin::Environment);
+    assert_eq!(
+        report.get("db.password").unwrap().value,
+        Some("<redacted>".to_string())
+    );
+    assert_eq!(report.get("missing"), None);
+}
+
+#[test]
+fn test_reports_selected_variant() {
+    let mut source = HashMap::new();
+    source.insert("REPORT_STORAGE".to_string(), "file".to_string());
+    source.insert("REPORT_FILE_PATH".to_string(), "/tmp".to_string());
+    let (_, report) = StorageConfig::init_from_hashmap_with_report(&source).unwrap();
+    let paths = report
+        .entries()
+        .iter()
+        .map(|entry| (entry.path.as_str(), entry.origin))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![
+            ("storage", Origin::HashMap),
+            ("storage.path", Origin::HashMap)
+        ]
+    );
+}
+
+#[test]
+fn test_reports_struct_default_origin() {
+    let (config, report) = DefaultConfig::init_from_hashmap_with_report(&HashMap::new()).unwrap();
+    assert_eq!(config.host, Some("localhost".to_string()));
+    assert_eq!(
+        report.entries(),
+        &[
+            Provenance {
+                path: "host".to_string(),
+                var: Some("REPORT_DEFAULT_HOST"),
+                origin: Origin::Default,
+                value: None,
+            },
+            Provenance {
+                path: "port".to_string(),
+                var: Some("REPORT_DEFAULT_PORT"),
+                origin: Origin::Default,
+                value: None,
+            },
+        ]
+    );
+}
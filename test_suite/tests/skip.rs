@@ -0,0 +1,75 @@
+extern crate envconfig;
+
+use envconfig::Envconfig;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+
+fn initial_cache() -> Mutex<Vec<String>> {
+    Mutex::new(vec!["warm".to_string()])
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(from = "DB_HOST")]
+    pub db_host: String,
+
+    #[envconfig(skip)]
+    pub requests_served: u64,
+
+    #[envconfig(skip, default_fn = "initial_cache")]
+    pub cache: Mutex<Vec<String>>,
+
+    #[envconfig(skip, default_expr = "1 + 1")]
+    pub replicas: u8,
+
+    #[envconfig(skip)]
+    pub marker: PhantomData<()>,
+}
+
+#[derive(Envconfig)]
+#[envconfig(default)]
+pub struct DefaultConfig {
+    #[envconfig(from = "PORT")]
+    pub port: u16,
+
+    #[envconfig(skip)]
+    pub label: String,
+}
+
+impl Default for DefaultConfig {
+    fn default() -> Self {
+        Self {
+            port: 80,
+            label: "default".to_string(),
+        }
+    }
+}
+
+#[test]
+fn test_skipped_fields_are_not_loaded() {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("DB_HOST".to_string(), "localhost".to_string());
+    hashmap.insert("REQUESTS_SERVED".to_string(), "100".to_string());
+    hashmap.insert("REPLICAS".to_string(), "5".to_string());
+
+    let config = Config::init_from_hashmap(&hashmap).unwrap();
+    assert_eq!(config.db_host, "localhost");
+    assert_eq!(config.requests_served, 0);
+    assert_eq!(*config.cache.lock().unwrap(), vec!["warm"]);
+    assert_eq!(config.replicas, 2);
+    assert_eq!(config.marker, PhantomData);
+}
+
+#[test]
+fn test_skipped_fields_use_struct_default() {
+    let config = DefaultConfig::init_from_hashmap(&HashMap::new()).unwrap();
+    assert_eq!(config.port, 80);
+    assert_eq!(config.label, "default");
+}
+
+#[test]
+fn test_skipped_fields_are_not_described() {
+    let info = Config::struct_info();
+    assert_eq!(info.var_names(), vec!["DB_HOST"]);
+}
@@ -0,0 +1,98 @@
+extern crate envconfig;
+
+use envconfig::testing::EnvGuard;
+use envconfig::{Envconfig, Error};
+
+#[derive(Envconfig)]
+pub struct DbConfig {
+    #[envconfig(from = "SNAPSHOT_DB_HOST")]
+    pub host: String,
+
+    #[envconfig(from = "SNAPSHOT_DB_PORT", default = "5432")]
+    pub port: u16,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(nested)]
+    pub db: DbConfig,
+
+    #[envconfig(from = "SNAPSHOT_BASE_URL")]
+    pub base_url: Option<String>,
+
+    #[envconfig(from = "SNAPSHOT_CALLBACK_URL", interpolate)]
+    pub callback_url: Option<String>,
+}
+
+#[test]
+fn test_inits_config_from_env_snapshot() {
+    let _env = EnvGuard::new()
+        .set("SNAPSHOT_DB_HOST", "localhost")
+        .remove("SNAPSHOT_DB_PORT")
+        .set("SNAPSHOT_BASE_URL", "https://example.com")
+        .set("SNAPSHOT_CALLBACK_URL", "${SNAPSHOT_BASE_URL}/callback");
+
+    let config = Config::init_from_env_snapshot().unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 5432);
+    assert_eq!(config.base_url.as_deref(), Some("https://example.com"));
+    assert_eq!(
+        config.callback_url.as_deref(),
+        Some("https://example.com/callback")
+    );
+}
+
+#[test]
+fn test_reports_errors_from_env_snapshot() {
+    let _env = EnvGuard::new()
+        .remove("SNAPSHOT_DB_HOST")
+        .set("SNAPSHOT_DB_PORT", "5432");
+
+    let err = Config::init_from_env_snapshot().err().unwrap();
+    assert_eq!(err.path(), Some("db.host"));
+    assert_eq!(err.var_name(), Some("SNAPSHOT_DB_HOST"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_reports_non_unicode_values_like_init_from_env() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let _env = EnvGuard::new()
+        .set("SNAPSHOT_DB_HOST", OsStr::from_bytes(b"local\xffhost"))
+        .remove("SNAPSHOT_DB_PORT");
+
+    let err = Config::init_from_env_snapshot().err().unwrap();
+    assert_eq!(err, Config::init_from_env().err().unwrap());
+    assert_eq!(
+        err,
+        Error::Nested {
+            path: "db.host".to_string(),
+            error: Box::new(Error::DecodeError {
+                name: "SNAPSHOT_DB_HOST".to_string(),
+            }),
+        }
+    );
+    assert_eq!(Config::init_from_env_with_report().err(), Some(err.clone()));
+    assert_eq!(Config::init_from_env_strict("SNAPSHOT_").err(), Some(err));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_reports_non_unicode_values_after_other_errors() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let _env = EnvGuard::new()
+        .remove("SNAPSHOT_DB_HOST")
+        .set("SNAPSHOT_BASE_URL", OsStr::from_bytes(b"x\xff"));
+
+    let err = Config::init_from_env_snapshot().err().unwrap();
+    assert_eq!(
+        err,
+        Error::DecodeError {
+            name: "SNAPSHOT_BASE_URL".to_string(),
+        }
+    );
+}
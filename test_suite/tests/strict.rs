@@ -0,0 +1,87 @@
+extern crate envconfig;
+
+use envconfig::testing::EnvGuard;
+use envconfig::{Envconfig, Error, UnknownVar};
+use std::collections::HashMap;
+
+#[derive(Envconfig)]
+pub struct DbConfig {
+    #[envconfig(from = "MYAPP_DB_HOST")]
+    pub host: String,
+
+    #[envconfig(from = "MYAPP_DB_PORT", default = "5432")]
+    pub port: u16,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(nested)]
+    pub db: DbConfig,
+
+    #[envconfig(from = "MYAPP_LOG_LEVEL")]
+    pub log_level: Option<String>,
+}
+
+#[test]
+fn test_accepts_known_and_unprefixed_vars() {
+    let mut source = HashMap::new();
+    source.insert("MYAPP_DB_HOST".to_string(), "localhost".to_string());
+    source.insert("MYAPP_LOG_LEVEL".to_string(), "debug".to_string());
+    source.insert("PATH".to_string(), "/usr/bin".to_string());
+    let config = Config::init_from_hashmap_strict(&source, "MYAPP_").unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.log_level.as_deref(), Some("debug"));
+}
+
+#[test]
+fn test_rejects_unknown_prefixed_vars() {
+    let mut source = HashMap::new();
+    source.insert("MYAPP_DB_HSOT".to_string(), "localhost".to_string());
+    source.insert("MYAPP_db_port".to_string(), "5432".to_string());
+    source.insert("MYAPP_UNRELATED".to_string(), "1".to_string());
+    let err = Config::init_from_hashmap_strict(&source, "MYAPP_")
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        Error::UnknownVars {
+            vars: vec![
+                UnknownVar {
+                    name: "MYAPP_DB_HSOT".to_string(),
+                    suggestions: vec!["MYAPP_DB_HOST"],
+                },
+                UnknownVar {
+                    name: "MYAPP_UNRELATED".to_string(),
+                    suggestions: vec![],
+                },
+                UnknownVar {
+                    name: "MYAPP_db_port".to_string(),
+                    suggestions: vec!["MYAPP_DB_PORT"],
+                },
+            ],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Unknown variables: MYAPP_DB_HSOT (did you mean MYAPP_DB_HOST?), MYAPP_UNRELATED, \
+         MYAPP_db_port (did you mean MYAPP_DB_PORT?)"
+    );
+}
+
+#[test]
+fn test_strict_env() {
+    let _env = EnvGuard::new()
+        .set("MYAPP_DB_HOST", "localhost")
+        .set("MYAPP_LOG_LEVL", "debug");
+
+    let err = Config::init_from_env_strict("MYAPP_").err().unwrap();
+    assert_eq!(
+        err,
+        Error::UnknownVars {
+            vars: vec![UnknownVar {
+                name: "MYAPP_LOG_LEVL".to_string(),
+                suggestions: vec!["MYAPP_LOG_LEVEL"],
+            }],
+        }
+    );
+}
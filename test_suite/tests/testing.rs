@@ -0,0 +1,60 @@
+extern crate envconfig;
+
+use envconfig::testing::{with_env, EnvGuard};
+use std::env;
+use std::panic;
+
+#[test]
+fn test_guard_restores_previous_values() {
+    // Only this test uses the variable, so it can be set without a guard
+    env::set_var("TESTING_EXISTING", "before");
+
+    let guard = EnvGuard::new().set("TESTING_EXISTING", "after");
+    assert_eq!(env::var("TESTING_EXISTING").unwrap(), "after");
+    drop(guard);
+    assert_eq!(env::var("TESTING_EXISTING").unwrap(), "before");
+
+    let guard = EnvGuard::new().remove("TESTING_EXISTING");
+    assert_eq!(env::var_os("TESTING_EXISTING"), None);
+    drop(guard);
+    assert_eq!(env::var("TESTING_EXISTING").unwrap(), "before");
+
+    env::remove_var("TESTING_EXISTING");
+}
+
+#[test]
+fn test_guard_restores_changed_and_removed_vars() {
+    let guard = EnvGuard::new()
+        .set("TESTING_SET", "1")
+        .set("TESTING_SET", "2")
+        .remove("TESTING_REMOVED");
+    assert_eq!(env::var("TESTING_SET").unwrap(), "2");
+    drop(guard);
+    assert_eq!(env::var_os("TESTING_SET"), None);
+}
+
+#[test]
+fn test_guard_restores_on_panic() {
+    let result = panic::catch_unwind(|| {
+        let _env = EnvGuard::new().set("TESTING_PANIC", "1");
+        panic!("test failure");
+    });
+    assert!(result.is_err());
+
+    // The lock is usable again and the variable is restored
+    let _env = EnvGuard::new();
+    assert_eq!(env::var_os("TESTING_PANIC"), None);
+}
+
+#[test]
+fn test_with_env() {
+    let value = with_env(
+        &[("TESTING_CLOSURE", Some("set")), ("TESTING_GONE", None)],
+        || (env::var("TESTING_CLOSURE"), env::var("TESTING_GONE")),
+    );
+    assert_eq!(value.0.unwrap(), "set");
+    assert!(value.1.is_err());
+
+    let _env = EnvGuard::new();
+    assert_eq!(env::var_os("TESTING_CLOSURE"), None);
+}
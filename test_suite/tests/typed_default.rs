@@ -0,0 +1,86 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, FieldInfo};
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn default_workers() -> usize {
+    4
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(from = "PORT", default = 8080)]
+    pub port: u16,
+
+    #[envconfig(from = "OFFSET", default = -1)]
+    pub offset: i32,
+
+    #[envconfig(from = "DEBUG", default = true)]
+    pub debug: bool,
+
+    #[envconfig(from = "WORKERS", default_fn = "default_workers")]
+    pub workers: usize,
+
+    #[envconfig(
+        from = "TIMEOUT_SECS",
+        default_expr = "Duration::from_secs(2).as_secs() * 15"
+    )]
+    pub timeout_secs: u64,
+
+    #[envconfig(from = "HOSTS", json, default_expr = "vec![\"localhost\".to_string()]")]
+    pub hosts: Vec<String>,
+}
+
+#[test]
+fn test_uses_typed_defaults() {
+    let config = Config::init_from_hashmap(&HashMap::new()).unwrap();
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.offset, -1);
+    assert!(config.debug);
+    assert_eq!(config.workers, 4);
+    assert_eq!(config.timeout_secs, 30);
+    assert_eq!(config.hosts, vec!["localhost"]);
+}
+
+#[test]
+fn test_variables_take_precedence() {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("PORT".to_string(), "9090".to_string());
+    hashmap.insert("OFFSET".to_string(), "5".to_string());
+    hashmap.insert("DEBUG".to_string(), "false".to_string());
+    hashmap.insert("WORKERS".to_string(), "16".to_string());
+    hashmap.insert("TIMEOUT_SECS".to_string(), "1".to_string());
+    hashmap.insert("HOSTS".to_string(), r#"["example.com"]"#.to_string());
+
+    let config = Config::init_from_hashmap(&hashmap).unwrap();
+    assert_eq!(config.port, 9090);
+    assert_eq!(config.offset, 5);
+    assert!(!config.debug);
+    assert_eq!(config.workers, 16);
+    assert_eq!(config.timeout_secs, 1);
+    assert_eq!(config.hosts, vec!["example.com"]);
+}
+
+#[test]
+fn test_describes_typed_defaults() {
+    let defaults = Config::struct_info()
+        .fields
+        .into_iter()
+        .map(|field| match field {
+            FieldInfo::Var(var) => (var.name, var.default, var.required),
+            FieldInfo::Nested { .. } | FieldInfo::Variants { .. } => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        defaults,
+        vec![
+            ("PORT", Some("8080"), false),
+            ("OFFSET", Some("-1"), false),
+            ("DEBUG", Some("true"), false),
+            ("WORKERS", None, false),
+            ("TIMEOUT_SECS", None, false),
+            ("HOSTS", None, false),
+        ]
+    );
+}
@@ -0,0 +1,142 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, Error};
+use std::time::Duration;
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(from = "TIMEOUT", duration)]
+    pub timeout: Duration,
+
+    #[envconfig(from = "IDLE_TIMEOUT", duration, default = "1h30m")]
+    pub idle_timeout: std::time::Duration,
+
+    #[envconfig(from = "RETRY_DELAY", duration)]
+    pub retry_delay: Option<Duration>,
+
+    #[envconfig(from = "BUFFER_SIZE", bytes, default = "64MiB")]
+    pub buffer_size: usize,
+
+    #[envconfig(from = "MAX_UPLOAD", bytes)]
+    pub max_upload: Option<u64>,
+
+    #[envconfig(from = "CHUNK_SIZE", bytes, default = "1KB")]
+    pub chunk_size: u16,
+}
+
+fn timeout(value: &str) -> Result<Duration, Error> {
+    Config::init_from_iter([("TIMEOUT", value)]).map(|config| config.timeout)
+}
+
+fn chunk_size(value: &str) -> Result<u16, Error> {
+    Config::init_from_iter([("TIMEOUT", "1s"), ("CHUNK_SIZE", value)])
+        .map(|config| config.chunk_size)
+}
+
+#[test]
+fn test_parses_durations() {
+    assert_eq!(timeout("30s"), Ok(Duration::from_secs(30)));
+    assert_eq!(timeout("5m"), Ok(Duration::from_mins(5)));
+    assert_eq!(timeout("1h30m"), Ok(Duration::from_mins(90)));
+    assert_eq!(timeout("250ms"), Ok(Duration::from_millis(250)));
+    assert_eq!(timeout("1m 15s"), Ok(Duration::from_secs(75)));
+    assert_eq!(timeout("2d"), Ok(Duration::from_hours(48)));
+    assert_eq!(timeout("10us"), Ok(Duration::from_micros(10)));
+}
+
+#[test]
+fn test_parses_byte_sizes() {
+    assert_eq!(chunk_size("512"), Ok(512));
+    assert_eq!(chunk_size("2KiB"), Ok(2048));
+    assert_eq!(chunk_size("8kb"), Ok(8000));
+    assert_eq!(chunk_size("64 B"), Ok(64));
+}
+
+#[test]
+fn test_defaults_and_optional_values() {
+    let config = Config::init_from_iter([("TIMEOUT", "1s")]).unwrap();
+    assert_eq!(config.idle_timeout, Duration::from_mins(90));
+    assert_eq!(config.retry_delay, None);
+    assert_eq!(config.buffer_size, 64 * 1024 * 1024);
+    assert_eq!(config.max_upload, None);
+    assert_eq!(config.chunk_size, 1000);
+
+    let config = Config::init_from_iter([
+        ("TIMEOUT", "1s"),
+        ("RETRY_DELAY", "100ms"),
+        ("MAX_UPLOAD", "1GB"),
+    ])
+    .unwrap();
+    assert_eq!(config.retry_delay, Some(Duration::from_millis(100)));
+    assert_eq!(config.max_upload, Some(1_000_000_000));
+}
+
+#[test]
+fn test_invalid_duration_errors() {
+    assert_eq!(
+        timeout("30"),
+        Err(Error::InvalidValue {
+            name: "TIMEOUT".to_string(),
+            value: "30".to_string(),
+            message: "missing unit, expected one of: ns, us, ms, s, m, h, d".to_string(),
+        })
+    );
+    assert_eq!(
+        timeout("5 minutes"),
+        Err(Error::InvalidValue {
+            name: "TIMEOUT".to_string(),
+            value: "5 minutes".to_string(),
+            message: "unknown unit, expected one of: ns, us, ms, s, m, h, d".to_string(),
+        })
+    );
+    assert!(matches!(timeout(""), Err(Error::InvalidValue { .. })));
+    assert!(matches!(timeout("1.5h"), Err(Error::InvalidValue { .. })));
+}
+
+#[test]
+fn test_invalid_byte_size_errors() {
+    assert_eq!(
+        chunk_size("1MiB"),
+        Err(Error::InvalidValue {
+            name: "CHUNK_SIZE".to_string(),
+            value: "1MiB".to_string(),
+            message: "byte size does not fit into the field type".to_string(),
+        })
+    );
+    assert!(matches!(chunk_size("1XB"), Err(Error::InvalidValue { .. })));
+    assert!(matches!(chunk_size("MiB"), Err(Error::InvalidValue { .. })));
+}
+
+#[test]
+fn test_check_defaults() {
+    assert_eq!(Config::check_defaults(), Ok(()));
+}
+
+mod custom {
+    use envconfig::Envconfig;
+    use std::str::FromStr;
+
+    /// A type named like `std::time::Duration`, parsed with its own `FromStr`
+    #[derive(Debug, PartialEq)]
+    pub struct Duration(pub u32);
+
+    impl FromStr for Duration {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse().map(Duration)
+        }
+    }
+
+    #[derive(Envconfig)]
+    pub struct Config {
+        #[envconfig(from = "CUSTOM_TIMEOUT")]
+        pub timeout: Duration,
+    }
+}
+
+#[test]
+fn test_parses_durations_only_with_attribute() {
+    let config = custom::Config::init_from_iter([("CUSTOM_TIMEOUT", "30")]).unwrap();
+    assert_eq!(config.timeout, custom::Duration(30));
+}
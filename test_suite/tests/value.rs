@@ -0,0 +1,92 @@
+extern crate envconfig;
+
+use envconfig::{Envconfig, EnvconfigValue, Error, InvalidValueError};
+use std::collections::HashMap;
+
+#[derive(EnvconfigValue, Debug, PartialEq)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    #[envconfig(rename = "warn")]
+    Warning,
+}
+
+#[derive(EnvconfigValue, Debug, PartialEq)]
+pub enum Region {
+    EuWest,
+    UsEast,
+}
+
+#[derive(Envconfig)]
+pub struct Config {
+    #[envconfig(from = "LOG_LEVEL", value, default = "info")]
+    pub log_level: LogLevel,
+
+    #[envconfig(from = "REGION", value)]
+    pub region: Option<Region>,
+}
+
+#[derive(Envconfig)]
+pub struct FromStrConfig {
+    #[envconfig(from = "LOG_LEVEL")]
+    pub log_level: LogLevel,
+}
+
+#[test]
+fn test_parses_variants_case_insensitively() {
+    assert_eq!("debug".parse::<LogLevel>(), Ok(LogLevel::Debug));
+    assert_eq!("INFO".parse::<LogLevel>(), Ok(LogLevel::Info));
+    assert_eq!("Warn".parse::<LogLevel>(), Ok(LogLevel::Warning));
+    assert_eq!("eu_west".parse::<Region>(), Ok(Region::EuWest));
+    assert_eq!("US_EAST".parse::<Region>(), Ok(Region::UsEast));
+}
+
+#[test]
+fn test_error_lists_accepted_values() {
+    let err = "warning".parse::<LogLevel>().unwrap_err();
+    assert_eq!(
+        err,
+        InvalidValueError {
+            value: "warning".to_string(),
+            choices: &["debug", "info", "warn"],
+        }
+    );
+    assert_eq!(err.to_string(), "expected one of: debug, info, warn");
+}
+
+#[test]
+fn test_loads_values_in_config() {
+    let mut hashmap = HashMap::new();
+    hashmap.insert("REGION".to_string(), "us_east".to_string());
+
+    let config = Config::init_from_hashmap(&hashmap).unwrap();
+    assert_eq!(config.log_level, LogLevel::Info);
+    assert_eq!(config.region, Some(Region::UsEast));
+
+    hashmap.insert("LOG_LEVEL".to_string(), "verbose".to_string());
+    let err = Config::init_from_hashmap(&hashmap).err().unwrap();
+    assert_eq!(
+        err,
+        Error::InvalidValue {
+            name: "LOG_LEVEL".to_string(),
+            value: "verbose".to_string(),
+            message: "expected one of: debug, info, warn".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_loads_values_with_from_str() {
+    let config = FromStrConfig::init_from_iter([("LOG_LEVEL", "debug")]).unwrap();
+    assert_eq!(config.log_level, LogLevel::Debug);
+
+    let err = FromStrConfig::init_from_iter([("LOG_LEVEL", "verbose")])
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        Error::ParseError {
+            name: "LOG_LEVEL".to_string()
+        }
+    );
+}